@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::common::json_string;
+use crate::manifest::{hash, relative_files, ManifestEntry, OutputDir};
+
+/// The name of the file in the output folder which maps each static file to its fingerprinted copy
+pub const ASSET_MANIFEST_FILE: &str = "asset-manifest.json";
+
+/// The number of hex digits of the content hash put in the name of a file
+const FINGERPRINT_LENGTH: usize = 8;
+
+/// Puts the fingerprint before the extension, e.g. `fonts/main.css` becomes `fonts/main.3f9a1c2b.css`
+fn fingerprinted_name(rel_path: &str, fingerprint: &str) -> String {
+    let file_start = rel_path.rfind('/').map_or(0, |i| i + 1);
+
+    // Hidden files (e.g. `.htaccess`) have no extension
+    match rel_path[file_start..].rfind('.') {
+        Some(i) if i > 0 => format!(
+            "{}.{}{}",
+            &rel_path[..file_start + i],
+            fingerprint,
+            &rel_path[file_start + i..]
+        ),
+        _ => format!("{}.{}", rel_path, fingerprint),
+    }
+}
+
+/// The static files along with the names of their fingerprinted copies (relative to the static folder).
+/// Pages link to the fingerprinted copies so that browsers never use an old version from their cache.
+#[derive(Debug, Clone, Default)]
+pub struct Assets {
+    files: BTreeMap<String, String>,
+}
+
+impl Assets {
+    /// Reads every static file to work out the name of its fingerprinted copy
+    pub fn load(static_files: &Path) -> io::Result<Assets> {
+        let mut files = BTreeMap::new();
+
+        for (path, rel_path) in relative_files(static_files)? {
+            let fingerprint = format!("{:016x}", hash(&fs::read(&path)?));
+            let name = fingerprinted_name(&rel_path, &fingerprint[..FINGERPRINT_LENGTH]);
+
+            files.insert(rel_path, name);
+        }
+
+        Ok(Assets { files })
+    }
+
+    /// Copies the static files into `rel_dir` under their fingerprinted names
+    pub fn copy(
+        &self,
+        output_dir: &OutputDir,
+        static_files: &Path,
+        rel_dir: &str,
+    ) -> io::Result<Vec<ManifestEntry>> {
+        self.files
+            .iter()
+            .map(|(rel_path, name)| {
+                output_dir.copy(
+                    &static_files.join(rel_path),
+                    &format!("{}/{}", rel_dir, name),
+                )
+            })
+            .collect()
+    }
+
+    /// The name of the copy of a static file which pages link to (relative to the static folder),
+    /// it is the name of the file itself if the static files aren't fingerprinted
+    pub fn name<'a>(&'a self, rel_path: &'a str) -> &'a str {
+        self.files.get(rel_path).map_or(rel_path, String::as_str)
+    }
+
+    /// A JSON object mapping the url of each static file to the url of its fingerprinted copy
+    pub fn manifest_json(&self, static_url: &str) -> String {
+        let entries: Vec<String> = self
+            .files
+            .iter()
+            .map(|(rel_path, name)| {
+                format!(
+                    "  {}: {}",
+                    json_string(&format!("{}/{}", static_url, rel_path)),
+                    json_string(&format!("{}/{}", static_url, name))
+                )
+            })
+            .collect();
+
+        format!("{{\n{}\n}}\n", entries.join(",\n"))
+    }
+}
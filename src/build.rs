@@ -1,125 +1,676 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io;
-use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::{Duration, Instant};
 
 use askama::Template;
+use notify::{DebouncedEvent, RecursiveMode, Watcher};
+use rayon::prelude::*;
 
-use crate::templates::{Home, Page};
+use crate::assets::{Assets, ASSET_MANIFEST_FILE};
+use crate::common::{CourseError, CourseFile, CourseGroup};
+use crate::config::{load_config, MarkdownSettings};
+use crate::feed::feeds;
+use crate::manifest::{hash, push_field, Manifest, ManifestEntry, OutputDir, MANIFEST_FILE};
+use crate::search::{search_index, SEARCH_INDEX_FILE};
+use crate::sitemap::{robots_txt, sitemap, ROBOTS_FILE, SITEMAP_FILE};
+use crate::taxonomy::Taxonomy;
+use crate::templates::{Home, LearningPath, Page, Redirect};
 
-/// Copys the contents on the input directory to the output directory.
-/// It creates all folders in the output path if they don't exist.
-fn copy_dir(input: &Path, output: &Path) -> io::Result<()> {
-    use walkdir::WalkDir;
+/// Settings for the build command
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    /// The url under which the output folder will live (see the help of the build command)
+    pub base_url: String,
+    /// Draft courses are only built if this is set
+    pub include_drafts: bool,
+    /// The number of courses built at the same time (0 uses one thread per CPU)
+    pub jobs: usize,
+    /// Keep the previous output and only write the files that changed (according to the manifest)
+    pub incremental: bool,
+    /// Delete the output folder even if it doesn't look like the output of a previous build
+    pub force: bool,
+    /// Only list the files that would be written and removed, without changing anything
+    pub dry_run: bool,
+    /// Also copy the static files with a hash of their contents in their names and link to those copies,
+    /// so that browsers don't keep using old versions from their cache
+    pub fingerprint_assets: bool,
+}
 
-    fs::create_dir_all(output)?;
+/// Converts a rendering error into an IO error so that it can be reported with the others
+fn render_error(err: askama::Error) -> io::Error {
+    io::Error::other(err.to_string())
+}
 
-    for entry in WalkDir::new(input) {
-        let path = entry?.path().to_owned();
+/// Returns why the output folder isn't safe to delete (or write into), if it isn't:
+/// it must be the output of a previous build (it contains the manifest) or empty,
+/// and it can't contain the input folder, the static folder, the current folder or the home folder.
+fn output_dir_problem(
+    output: &Path,
+    input: &Path,
+    static_files: &Path,
+) -> io::Result<Option<String>> {
+    if !output.exists() {
+        return Ok(None);
+    }
+
+    if !output.is_dir() {
+        return Ok(Some("It isn't a folder".to_string()));
+    }
 
-        let rel_path = path
-            .strip_prefix(input)
-            .expect("Couldn't get relative path");
+    let output = output.canonicalize()?;
 
-        if path.is_dir() {
-            fs::create_dir_all(output.join(rel_path))?;
-        } else if path.is_file() {
-            // Copy files from input static to output
-            std::fs::copy(path.clone(), output.join(rel_path))?;
+    let protected: Vec<(&str, Option<PathBuf>)> = vec![
+        ("the input folder", Some(input.canonicalize()?)),
+        ("the static folder", Some(static_files.canonicalize()?)),
+        ("the current folder", std::env::current_dir().ok()),
+        (
+            "the home folder",
+            std::env::var_os("HOME").map(PathBuf::from),
+        ),
+    ];
+
+    for (name, folder) in protected {
+        let folder = match folder.map(|folder| folder.canonicalize().unwrap_or(folder)) {
+            Some(folder) => folder,
+            None => continue,
+        };
+
+        if folder.starts_with(&output) {
+            return Ok(Some(format!("It is or contains {} ({:?})", name, folder)));
         }
     }
 
-    Ok(())
+    if !output.join(MANIFEST_FILE).is_file() && fs::read_dir(&output)?.next().is_some() {
+        return Ok(Some(format!(
+            "It isn't empty and doesn't look like the output of a previous build (there is no {} file)",
+            MANIFEST_FILE
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Fails if the output folder isn't safe to delete, unless the build is forced
+pub fn check_output_dir<P: AsRef<Path>>(
+    input: P,
+    static_files: P,
+    output: P,
+    options: &BuildOptions,
+) -> Result<(), CourseError> {
+    if options.force {
+        return Ok(());
+    }
+
+    match output_dir_problem(output.as_ref(), input.as_ref(), static_files.as_ref())? {
+        Some(problem) => Err(CourseError::Parse(format!(
+            "Refusing to use {:?} as the output folder: {}\nUse --force to delete it and build there anyway.",
+            output.as_ref(),
+            problem
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// A hash of everything other than the course itself that the page of a course depends on:
+/// the program (which contains the templates), the base url, the groups, the learning paths,
+/// the title, url and order of every course (these are used by the links to other courses)
+/// the names of the fingerprinted static files and the markdown settings (the courses are parsed with them).
+fn site_hash(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    paths: &BTreeMap<String, LearningPath>,
+    base_url: &str,
+    assets: &Assets,
+    markdown: &MarkdownSettings,
+) -> u64 {
+    // If the program can't be read the version is the best that can be done
+    let program = std::env::current_exe()
+        .and_then(fs::read)
+        .unwrap_or_else(|_| env!("CARGO_PKG_VERSION").as_bytes().to_vec());
+
+    // Every field is written out explicitly since the `Debug` format may change between versions of Rust
+    let mut input = Vec::new();
+    let mut push = |field: &str| push_field(&mut input, field.as_bytes());
+
+    push(base_url);
+    push(&assets.manifest_json(""));
+
+    for extension in &["tables", "footnotes", "strikethrough", "tasklists"] {
+        push(&markdown.is_enabled(extension).to_string());
+    }
+
+    for path in paths.values() {
+        push(&path.name);
+        push(&path.title);
+        push(path.description.as_deref().unwrap_or(""));
+        push(&path.courses.join("\n"));
+    }
+
+    for (group_name, group) in course_groups {
+        let info = &group.info;
+
+        push(group_name);
+        push(&info.title);
+        push(info.description.as_deref().unwrap_or(""));
+        push(info.image.as_deref().unwrap_or(""));
+        push(&info.weight.to_string());
+        push(&info.course_order.join("\n"));
+
+        for (url, course_file) in &group.courses {
+            let course = &course_file.course;
+
+            push(url);
+            push(course_file.name());
+            push(&course.title);
+            push(&course.weight.to_string());
+            push(&course.unlisted.to_string());
+        }
+    }
+
+    push_field(&mut input, &program);
+
+    hash(&input)
 }
 
 /// Builds the output folder containing a copy of the static files and the HTML render of all the courses.
 /// Also builds an index page.
+/// A manifest of every file written is kept in the output folder so that incremental builds
+/// only write the files that changed and delete the ones whose source was removed.
+/// The manifest also marks the folder as safe to delete, any other folder is left alone unless `force` is set.
 pub fn build_html<P: AsRef<Path>>(
     input: P,
     static_files: P,
     output: P,
-    base_url: String,
-) -> io::Result<()> {
-    use crate::common::{get_courses, CourseError};
-    let course_groups_paths = match get_courses(input, true) {
-        Ok(courses) => courses,
-        Err(CourseError::Io(err)) => return Err(err),
-        Err(CourseError::Parse(msg)) => {
-            println!("Build process failed:\n{}", msg);
-            std::process::exit(1);
-        }
+    options: &BuildOptions,
+) -> Result<(), CourseError> {
+    use crate::common::{
+        alias_reference, breadcrumbs, course_href, course_index, get_courses, home_groups,
+        prerequisite_links, resolve_course_links, sibling_links,
+    };
+    use crate::learning_paths::{get_paths, path_navs, path_page, paths_overview, PATHS_FOLDER};
+    let site_config = load_config(input.as_ref())?;
+    let course_groups_paths =
+        get_courses(input.as_ref(), &site_config, true, options.include_drafts)?;
+    let paths = get_paths(input.as_ref(), &course_groups_paths, true)?;
+
+    let base_url = &options.base_url;
+
+    check_output_dir(
+        input.as_ref(),
+        static_files.as_ref(),
+        output.as_ref(),
+        options,
+    )?;
+
+    // Without a manifest there is no way to know what is out of date so everything is rebuilt
+    let previous_manifest = if options.incremental {
+        Manifest::load(output.as_ref())
+    } else {
+        None
     };
 
-    // Delete existing output files
-    if output.as_ref().is_dir() {
-        fs::remove_dir_all(output.as_ref())?;
+    if previous_manifest.is_none() {
+        // Delete existing output files
+        if output.as_ref().is_dir() {
+            if options.dry_run {
+                println!("Would delete {:?}", output.as_ref());
+            } else {
+                fs::remove_dir_all(output.as_ref())?;
+            }
+        }
     }
 
     // Create empty folder
-    fs::create_dir_all(output.as_ref())?;
+    if !options.dry_run {
+        fs::create_dir_all(output.as_ref())?;
+
+        // The folder is marked as the output of a build straight away (the full manifest is saved at the end),
+        // otherwise a build that fails part way through leaves a folder that later builds refuse to delete
+        if previous_manifest.is_none() {
+            Manifest::default().save(output.as_ref())?;
+        }
+    }
+
+    let output_dir = OutputDir::new(output.as_ref(), previous_manifest.as_ref(), options.dry_run);
+    let mut manifest = Manifest::default();
+
+    // Every page links to the static files so the fingerprinted names are needed first
+    let assets = if options.fingerprint_assets {
+        Assets::load(static_files.as_ref())?
+    } else {
+        Assets::default()
+    };
+    let static_url = format!("{}/static", base_url);
 
     // ==Handle courses==
-    let course_dir = output.as_ref().join("course");
 
-    // This is used for the home page
-    let mut course_index = HashMap::new();
+    // Every course has already been parsed, pages link to other courses (e.g. prerequisites) so they are all needed
+    let all_courses = course_index(&course_groups_paths);
+
+    for (course_group_name, course_group) in &course_groups_paths {
+        // The group image is linked to from the home page
+        if let Some(image) = &course_group.info.image {
+            manifest.extend(Some(output_dir.copy(
+                &course_group.path.join(image),
+                &format!("group/{}/{}", course_group_name, image),
+            )?));
+        }
+    }
+
+    let site_hash = site_hash(
+        &course_groups_paths,
+        &paths,
+        base_url,
+        &assets,
+        &site_config.markdown,
+    );
+
+    // Every course writes to its own folder so they can be built in any order
+    let course_files: Vec<(&String, &String, &CourseFile)> = course_groups_paths
+        .iter()
+        .flat_map(|(course_group_name, course_group)| {
+            course_group
+                .courses
+                .iter()
+                .map(move |(course_name, course_file)| {
+                    (course_group_name, course_name, course_file)
+                })
+        })
+        .collect();
+
+    let build_course = |(course_group_name, course_name, course_file): &(
+        &String,
+        &String,
+        &CourseFile,
+    )|
+     -> io::Result<Vec<ManifestEntry>> {
+        let rel_path = format!("{}/{}", course_group_name, course_name);
 
-    for (course_group_name, courses) in course_groups_paths {
-        for (course_name, course_path) in courses {
-            let rel_path = format!("{}/{}", course_group_name, course_name);
+        // The page only needs to be rendered again if the course file or something else it depends on changed
+        let mut course_path = course_file.path.clone().into_os_string();
+        course_path.push(".yml");
 
-            fs::create_dir_all(course_dir.join(rel_path.clone()))?;
+        let mut input = site_hash.to_le_bytes().to_vec();
+        push_field(&mut input, &fs::read(course_path)?);
+        let page_hash = hash(&input);
 
-            let course_str = std::fs::read_to_string(course_path.with_extension("yml"))
-                .expect("Couldn't open and read course file");
-            let course = crate::parse::parse_course(&course_str).expect("Couldn't parse yaml file");
+        let mut entries = vec![output_dir.write_with(
+            &format!("course/{}/index.html", rel_path),
+            page_hash,
+            || {
+                let course = course_file.course.clone();
+                let (previous_course, next_course) = sibling_links(
+                    &course_groups_paths,
+                    &all_courses,
+                    course_group_name,
+                    course_name,
+                    base_url,
+                );
 
-            // Append this course to the index
-            course_index
-                .entry(course_group_name.clone())
-                .or_insert_with(HashMap::new)
-                .insert(course_name, course.clone());
+                let page = Page {
+                    base_url: base_url.clone(),
+                    assets: assets.clone(),
+                    breadcrumbs: breadcrumbs(&course_groups_paths, course_group_name),
+                    prerequisites: prerequisite_links(&course, &all_courses, base_url),
+                    paths: path_navs(&rel_path, &paths, &all_courses, base_url),
+                    previous_course,
+                    next_course,
+                    course,
+                };
 
-            let page = Page {
-                base_url: base_url.clone(),
-                course,
+                Ok(resolve_course_links(
+                    &page.render().map_err(render_error)?,
+                    &course_groups_paths,
+                    base_url,
+                ))
+            },
+        )?];
+
+        // The old urls of the course redirect to it
+        let href = course_href(base_url, &rel_path);
+        let canonical = match &site_config.site_origin {
+            Some(site_origin) => format!("{}{}", site_origin, href),
+            None => href.clone(),
+        };
+
+        for alias in &course_file.course.aliases {
+            let redirect = Redirect {
+                title: course_file.course.title.clone(),
+                href: href.clone(),
+                canonical: canonical.clone(),
             };
 
-            let html = page.render().expect("Couldn't render course");
+            entries.push(output_dir.write(
+                &format!(
+                    "course/{}/index.html",
+                    alias_reference(course_group_name, alias)
+                ),
+                redirect.render().map_err(render_error)?,
+            )?);
+        }
 
-            let mut file = fs::File::create(course_dir.join(format!("{}/index.html", rel_path)))?;
-            file.write_all(html.as_bytes())?;
+        // This directory is the assets folder
+        if course_file.path.is_dir() {
+            entries.extend(
+                output_dir.copy_dir(&course_file.path, &format!("course/{}/assets", rel_path))?,
+            );
+        }
 
-            // This directory is the assets folder
-            if course_path.is_dir() {
-                copy_dir(
-                    &course_path,
-                    &course_dir.join(format!("{}/assets", rel_path)),
-                )?;
-            }
+        Ok(entries)
+    };
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.jobs)
+        .build()
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+    // The results are collected in order so the errors are always reported in the same order
+    let results: Vec<io::Result<Vec<ManifestEntry>>> =
+        pool.install(|| course_files.par_iter().map(build_course).collect());
+
+    let mut errors = Vec::new();
+
+    for ((course_group_name, course_name, _), result) in course_files.iter().zip(results) {
+        match result {
+            Ok(entries) => manifest.extend(entries),
+            Err(err) => errors.push(format!("{}/{}: {}", course_group_name, course_name, err)),
         }
     }
 
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("ERROR ========= Unable to build the course {}\n", error);
+        }
+
+        return Err(CourseError::Io(io::Error::other(format!(
+            "{} course(s) couldn't be built",
+            errors.len()
+        ))));
+    }
+
     // ==Handle static files==
-    let static_output = output.as_ref().join("static");
 
-    copy_dir(static_files.as_ref(), &static_output).expect("Couldn't copy static files");
+    // The original names are kept as well so that links between static files (e.g. to the fonts in fonts.css)
+    // and pages from a previous build which are still cached keep working
+    manifest.extend(
+        output_dir
+            .copy_dir(static_files.as_ref(), "static")
+            .expect("Couldn't copy static files"),
+    );
+
+    if options.fingerprint_assets {
+        manifest.extend(assets.copy(&output_dir, static_files.as_ref(), "static")?);
+        manifest.extend(Some(
+            output_dir.write(ASSET_MANIFEST_FILE, assets.manifest_json(&static_url))?,
+        ));
+    }
+
+    // ==Handle tag and author pages==
+    for taxonomy in &Taxonomy::ALL {
+        let html = taxonomy
+            .overview(base_url, &assets, &all_courses)
+            .render()
+            .map_err(render_error)?;
+
+        manifest.extend(Some(
+            output_dir.write(&format!("{}/index.html", taxonomy.folder()), html)?,
+        ));
+
+        for (slug, (name, courses)) in taxonomy.index(&all_courses) {
+            let html = taxonomy
+                .page(base_url, &assets, &course_groups_paths, &name, courses)
+                .render()
+                .map_err(render_error)?;
+
+            manifest.extend(Some(
+                output_dir.write(&format!("{}/{}/index.html", taxonomy.folder(), slug), html)?,
+            ));
+        }
+    }
+
+    // ==Handle learning paths==
+    let html = paths_overview(&paths, base_url, &assets)
+        .render()
+        .map_err(render_error)?;
+
+    manifest.extend(Some(
+        output_dir.write(&format!("{}/index.html", PATHS_FOLDER), html)?,
+    ));
+
+    for (name, path) in &paths {
+        let html = path_page(path, &all_courses, base_url, &assets)
+            .render()
+            .map_err(render_error)?;
+
+        manifest.extend(Some(
+            output_dir.write(&format!("{}/{}/index.html", PATHS_FOLDER, name), html)?,
+        ));
+    }
+
+    // ==Handle search index==
+    manifest.extend(Some(
+        output_dir.write(SEARCH_INDEX_FILE, search_index(&all_courses, base_url))?,
+    ));
 
     // ==Handle home page==
 
     let home = Home {
-        base_url,
-        course_groups: course_index,
+        base_url: base_url.clone(),
+        assets: assets.clone(),
+        heading: None,
+        course_groups: home_groups(&course_groups_paths, all_courses),
+        feeds: site_config.site_origin.is_some(),
     };
 
-    let html = home.render().expect("Couldn't render home page");
+    manifest.extend(Some(
+        output_dir.write("index.html", home.render().map_err(render_error)?)?,
+    ));
+
+    // ==Handle 404 page==
+
+    // Web servers can be set up to show this for any missing page, it lists every course like the home page
+    let not_found = Home {
+        heading: Some("Page not found".to_string()),
+        ..home
+    };
+
+    manifest.extend(Some(
+        output_dir.write("404.html", not_found.render().map_err(render_error)?)?,
+    ));
+
+    // ==Handle sitemap and robots.txt==
+
+    // The sitemap needs full urls so it is only built if the config says where the site is served from
+    if let Some(site_origin) = &site_config.site_origin {
+        let xml = sitemap(&course_groups_paths, site_origin, base_url)
+            .render()
+            .map_err(render_error)?;
+
+        manifest.extend(Some(output_dir.write(SITEMAP_FILE, xml)?));
+
+        // Search engines only read robots.txt from the root of the site
+        if site_config.robots_txt && !base_url.is_empty() {
+            println!(
+                "WARNING ========= robots.txt wasn't written since the base url is {:?}, \
+search engines only read it from the root of the site (e.g. {}/robots.txt)\n",
+                base_url, site_origin
+            );
+        } else if site_config.robots_txt {
+            let txt = robots_txt(site_origin, base_url)
+                .render()
+                .map_err(render_error)?;
+
+            manifest.extend(Some(output_dir.write(ROBOTS_FILE, txt)?));
+        }
+
+        // ==Handle feeds==
+        for (rel_path, feed) in feeds(&course_groups_paths, site_origin, base_url)? {
+            let xml = feed.render().map_err(render_error)?;
+
+            manifest.extend(Some(output_dir.write(&rel_path, xml)?));
+        }
+    }
+
+    let written = output_dir.written();
+
+    if options.dry_run {
+        for path in &written {
+            println!("Would write {}", path);
+        }
+
+        let stale = match &previous_manifest {
+            Some(previous_manifest) => manifest.stale_files(previous_manifest),
+            None => Vec::new(),
+        };
+
+        for path in &stale {
+            println!("Would remove {}", path);
+        }
+
+        println!(
+            "Dry run of building to {:?} ({} files would be written, {} removed), nothing was changed",
+            output.as_ref(),
+            written.len(),
+            stale.len()
+        );
+
+        return Ok(());
+    }
+
+    // ==Remove the files from the previous build that no longer have a source==
+    let removed = match &previous_manifest {
+        Some(previous_manifest) => manifest.remove_stale(previous_manifest, output.as_ref())?,
+        None => 0,
+    };
 
-    let mut index = fs::File::create(output.as_ref().join("index.html"))?;
-    index.write_all(html.as_bytes())?;
+    manifest.save(output.as_ref())?;
 
     // Done
-    println!("Built to {:?}", output.as_ref());
+    if options.incremental {
+        println!(
+            "Built to {:?} ({} files written, {} unchanged, {} removed)",
+            output.as_ref(),
+            written.len(),
+            manifest.file_count() - written.len(),
+            removed
+        );
+    } else {
+        println!("Built to {:?}", output.as_ref());
+    }
+
+    Ok(())
+}
+
+/// Converts an error from the file watcher into an IO error
+fn watch_error(err: notify::Error) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+/// The file which an event is about, None if the event doesn't change anything in the input
+/// (e.g. the build writing into an output folder which is inside the course folder)
+fn changed_path(event: DebouncedEvent, input: &Path, output: &Path) -> Option<PathBuf> {
+    let path = match event {
+        DebouncedEvent::Create(path)
+        | DebouncedEvent::Write(path)
+        | DebouncedEvent::Remove(path)
+        | DebouncedEvent::Rename(_, path) => path,
+        // Some events were lost so anything could have changed
+        DebouncedEvent::Rescan => input.to_path_buf(),
+        DebouncedEvent::Error(err, path) => {
+            println!("WARNING ========= Error while watching {:?}: {}", path, err);
+            return None;
+        }
+        DebouncedEvent::NoticeWrite(_)
+        | DebouncedEvent::NoticeRemove(_)
+        | DebouncedEvent::Chmod(_) => return None,
+    };
+
+    if path.starts_with(output) {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+/// Builds once and then builds again (incrementally) every time something in the input or static folder changes.
+/// Errors are printed and the previous output is left as it is until the next change.
+pub fn watch<P: AsRef<Path>>(
+    input: P,
+    static_files: P,
+    output: P,
+    options: &BuildOptions,
+) -> Result<(), CourseError> {
+    // Checked first so that an unsafe output folder stops the command instead of being reported on every change
+    check_output_dir(
+        input.as_ref(),
+        static_files.as_ref(),
+        output.as_ref(),
+        options,
+    )?;
+
+    let input = input.as_ref().canonicalize()?;
+    let static_files = static_files.as_ref().canonicalize()?;
+    let output = output.as_ref().to_path_buf();
+
+    let rebuild = |options: &BuildOptions| {
+        let start = Instant::now();
+
+        match build_html(&input, &static_files, &output, options) {
+            Ok(()) => println!("Done in {}ms", start.elapsed().as_millis()),
+            Err(CourseError::Io(err)) => println!("Build failed: {}", err),
+            Err(CourseError::Parse(msg)) => println!("Build failed:\n{}", msg),
+        }
+    };
+
+    rebuild(options);
+
+    // Everything after the first build only writes what changed
+    let options = BuildOptions {
+        incremental: true,
+        ..options.clone()
+    };
+
+    // The events use absolute paths
+    let output_folder = output.canonicalize().unwrap_or_else(|_| output.clone());
+
+    let (sender, receiver) = channel();
+    // Changes are grouped together so that saving several files only causes one build
+    let mut watcher = notify::watcher(sender, Duration::from_millis(300)).map_err(watch_error)?;
+
+    watcher
+        .watch(&input, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+    watcher
+        .watch(&static_files, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    println!(
+        "Watching {:?} and {:?} for changes (press Ctrl-C to stop)",
+        input, static_files
+    );
+
+    for event in receiver.iter() {
+        let mut changed: Vec<PathBuf> = std::iter::once(event)
+            .chain(receiver.try_iter())
+            .filter_map(|event| changed_path(event, &input, &output_folder))
+            .collect();
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        changed.sort();
+        changed.dedup();
+
+        match changed.len() {
+            1 => println!("\nChanged {:?}", changed[0]),
+            count => println!("\nChanged {:?} and {} other file(s)", changed[0], count - 1),
+        }
+
+        rebuild(&options);
+    }
 
     Ok(())
 }
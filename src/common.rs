@@ -1,7 +1,59 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
+use crate::config::SiteConfig;
+use crate::learning_paths::PATHS_FOLDER;
 use crate::parse::ParseError;
+use crate::templates::{Breadcrumb, Course, GroupInfo, HomeGroup, NavLink};
+
+/// The name of the (optional) file in a group folder that describes the group
+pub const GROUP_FILE: &str = "_group.yml";
+
+/// A map of group name to a map of url to course (the same shape as the index used for the home page)
+pub type CourseIndex = BTreeMap<String, BTreeMap<String, Course>>;
+
+/// A folder of courses
+#[derive(Debug, Clone)]
+pub struct CourseGroup {
+    /// The path to the group folder
+    pub path: PathBuf,
+    pub info: GroupInfo,
+    /// A map of urls to course
+    pub courses: BTreeMap<String, CourseFile>,
+}
+
+/// A course along with the file it was parsed from
+#[derive(Debug, Clone)]
+pub struct CourseFile {
+    /// The path to the course file excluding the .yml (which is also the path to the assets folder)
+    pub path: PathBuf,
+    pub course: Course,
+}
+
+impl CourseFile {
+    /// The name of the course file (excluding the .yml), this is used in links and in group files
+    pub fn name(&self) -> &str {
+        self.path.file_name().and_then(OsStr::to_str).unwrap_or("")
+    }
+}
+
+/// Returns a map of group name to a map of url to course, the courses are cloned out of the groups
+pub fn course_index(course_groups: &BTreeMap<String, CourseGroup>) -> CourseIndex {
+    course_groups
+        .iter()
+        .filter(|(_, group)| !group.courses.is_empty())
+        .map(|(group_name, group)| {
+            let courses = group
+                .courses
+                .iter()
+                .map(|(url, course_file)| (url.clone(), course_file.course.clone()))
+                .collect();
+
+            (group_name.clone(), courses)
+        })
+        .collect()
+}
 
 pub fn generate_error_message(mut output: &mut String, file: &str, err: ParseError) {
     use std::fmt::Write;
@@ -19,9 +71,17 @@ but it is possible the actual error is on a previous line and it was only detect
             )
             .unwrap();
 
-            let line = file.lines().nth(location.line() - 1).unwrap();
-            writeln!(&mut output, "{}", line).unwrap();
-            writeln!(&mut output, "{: <1$}^^^", "", location.col()).unwrap();
+            // Errors at the end of the file (e.g. an unclosed bracket) are after the last line
+            let line = location
+                .line()
+                .checked_sub(1)
+                .and_then(|index| file.lines().nth(index));
+
+            if let Some(line) = line {
+                writeln!(&mut output, "{}", line).unwrap();
+                writeln!(&mut output, "{: <1$}^^^", "", location.col()).unwrap();
+            }
+
             writeln!(&mut output, "Error: {} ({})", msg, scan).unwrap();
         }
         _ => {
@@ -31,6 +91,160 @@ but it is possible the actual error is on a previous line and it was only detect
     }
 }
 
+/// Whether the path is relative and stays inside the folder it is relative to,
+/// i.e. it isn't absolute and none of its segments are empty, `.` or `..`
+pub fn is_contained_path(path: &str) -> bool {
+    path.split(&['/', '\\'][..])
+        .all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+}
+
+/// Escapes text so that it can be put in HTML (including inside a double quoted attribute)
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Converts text into a form suitable for ids and urls, e.g. `Print statements!` becomes `print-statements`
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    // Remove the trailing separator (if there is one)
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+
+    if slug.is_empty() {
+        // Make sure there is always something to link to
+        slug.push_str("section");
+    }
+
+    slug
+}
+
+/// Links to other courses (or other parts of the same course) start with this, e.g. `course:python/introduction#variables`
+pub const COURSE_LINK_SCHEME: &str = "course:";
+
+/// Finds a course by the `group/url` used for prerequisites and learning paths
+pub fn find_course<'a>(course_index: &'a CourseIndex, reference: &str) -> Option<&'a Course> {
+    let split = reference.rfind('/')?;
+
+    course_index
+        .get(&reference[..split])?
+        .get(&reference[split + 1..])
+}
+
+/// The url of the page of a course, `reference` is the `group/url` of the course
+pub fn course_href(base_url: &str, reference: &str) -> String {
+    format!("{}/course/{}/index.html", base_url, reference)
+}
+
+/// A link to a course, None if the course doesn't exist (e.g. it is a draft that was left out)
+pub fn course_nav_link(
+    course_index: &CourseIndex,
+    reference: &str,
+    base_url: &str,
+) -> Option<NavLink> {
+    find_course(course_index, reference).map(|course| NavLink {
+        title: course.title.clone(),
+        href: course_href(base_url, reference),
+    })
+}
+
+/// Links to the prerequisites of a course (any that don't exist have already been reported when the courses were loaded)
+pub fn prerequisite_links(
+    course: &Course,
+    course_index: &CourseIndex,
+    base_url: &str,
+) -> Vec<NavLink> {
+    course
+        .prerequisites
+        .iter()
+        .filter_map(|reference| course_nav_link(course_index, reference, base_url))
+        .collect()
+}
+
+/// Splits the href of a `course:` link into the course (`group/name`, which is empty for links within the same course)
+/// and the optional fragment. Returns None if the href is not a `course:` link.
+pub fn split_course_link(href: &str) -> Option<(&str, Option<&str>)> {
+    if !href.starts_with(COURSE_LINK_SCHEME) {
+        return None;
+    }
+
+    let link = &href[COURSE_LINK_SCHEME.len()..];
+
+    Some(match link.find('#') {
+        Some(i) => (&link[..i], Some(&link[i + 1..])),
+        None => (link, None),
+    })
+}
+
+/// Finds the url (relative to the course directory) of a course from the `group/name` used in links,
+/// where `name` is the name of the course file (excluding the .yml) so that links survive `url` changes
+fn linked_course_url(
+    course: &str,
+    course_groups: &BTreeMap<String, CourseGroup>,
+) -> Option<String> {
+    let split = course.rfind('/')?;
+    let (group, name) = (&course[..split], &course[split + 1..]);
+
+    course_groups
+        .get(group)?
+        .courses
+        .iter()
+        .find(|(_, course_file)| course_file.name() == name)
+        .map(|(url, _)| format!("{}/{}", group, url))
+}
+
+/// Rewrites the hrefs of the `course:` links in rendered HTML into real urls.
+/// Links that can't be resolved are left alone (they are reported by `get_courses`).
+pub fn resolve_course_links(
+    html: &str,
+    course_groups: &BTreeMap<String, CourseGroup>,
+    base_url: &str,
+) -> String {
+    let pattern = format!("href=\"{}", COURSE_LINK_SCHEME);
+
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&pattern) {
+        // Keep everything up to and including the opening quote
+        let (before, after) = rest.split_at(start + "href=\"".len());
+        output.push_str(before);
+
+        let end = after.find('"').unwrap_or(after.len());
+        let href = &after[..end];
+
+        let resolved = split_course_link(href).and_then(|(course, fragment)| {
+            let fragment = fragment.map(|f| format!("#{}", f)).unwrap_or_default();
+
+            if course.is_empty() {
+                Some(fragment)
+            } else {
+                linked_course_url(course, course_groups)
+                    .map(|url| format!("{}/course/{}/index.html{}", base_url, url, fragment))
+            }
+        });
+
+        output.push_str(resolved.as_deref().unwrap_or(href));
+        rest = &after[end..];
+    }
+
+    output.push_str(rest);
+
+    output
+}
+
 pub enum CourseError {
     Io(std::io::Error),
     Parse(String),
@@ -42,15 +256,439 @@ impl From<std::io::Error> for CourseError {
     }
 }
 
-/// Returns a hashmap of urls to path to course (excluding the .yml)
+/// Reads the group file in the group folder (if there is one), otherwise the defaults are used
+fn get_group_info(
+    group_folder: &Path,
+    group_name: &str,
+    strict_mode: bool,
+) -> Result<GroupInfo, CourseError> {
+    let group_path = group_folder.join(GROUP_FILE);
+
+    if !group_path.is_file() {
+        return Ok(GroupInfo::new(group_name));
+    }
+
+    let group_str = std::fs::read_to_string(&group_path)?;
+
+    let problem = match crate::parse::parse_group(&group_str, group_name) {
+        Ok(info) => match &info.image {
+            Some(image) if !group_folder.join(image).is_file() => {
+                format!(
+                    "Error: Couldn't find the image `{}` in the group folder\n",
+                    image
+                )
+            }
+            _ => return Ok(info),
+        },
+        Err(err) => {
+            let mut msg = String::new();
+            generate_error_message(&mut msg, &group_str, err);
+            msg
+        }
+    };
+
+    let msg = format!(
+        "{} ========= Unable to parse: {:?}\n{}",
+        if strict_mode { "FATAL" } else { "WARNING" },
+        &group_path,
+        problem
+    );
+
+    if strict_mode {
+        Err(CourseError::Parse(msg))
+    } else {
+        // Print the message and continue with the defaults
+        println!("{}\n", msg);
+        Ok(GroupInfo::new(group_name))
+    }
+}
+
+/// The id of a group on the home page (used by breadcrumbs)
+pub fn group_anchor(group_name: &str) -> String {
+    format!("group-{}", slugify(group_name))
+}
+
+/// The titles of the groups containing the course group (including itself) starting from the top level group
+pub fn breadcrumbs(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    group_name: &str,
+) -> Vec<Breadcrumb> {
+    group_name
+        .match_indices('/')
+        .map(|(i, _)| &group_name[..i])
+        .chain(std::iter::once(group_name))
+        .map(|name| Breadcrumb {
+            title: course_groups
+                .get(name)
+                .map(|group| group.info.title.clone())
+                .unwrap_or_else(|| name.to_string()),
+            anchor: group_anchor(name),
+        })
+        .collect()
+}
+
+/// Sorts the courses (url and course) of a group into the order given by the group file
+/// followed by the rest sorted by weight then title.
+fn sort_group_courses(group: &CourseGroup, courses: &mut [(String, Course)]) {
+    courses.sort_by_cached_key(|(url, course)| {
+        let file_name = group.courses.get(url).map(CourseFile::name).unwrap_or("");
+
+        let position = group
+            .info
+            .course_order
+            .iter()
+            .position(|name| name == file_name)
+            .unwrap_or(usize::MAX);
+
+        (position, course.weight, course.title.clone(), url.clone())
+    });
+}
+
+/// The courses before and after a course in its group, in the order they are listed on the home page.
+/// Unlisted courses aren't part of the order so they have neither.
+pub fn sibling_links(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    course_index: &CourseIndex,
+    group_name: &str,
+    url: &str,
+    base_url: &str,
+) -> (Option<NavLink>, Option<NavLink>) {
+    let (group, courses) = match (course_groups.get(group_name), course_index.get(group_name)) {
+        (Some(group), Some(courses)) => (group, courses),
+        _ => return (None, None),
+    };
+
+    let mut siblings: Vec<(String, Course)> = courses
+        .iter()
+        .filter(|(_, course)| !course.unlisted)
+        .map(|(url, course)| (url.clone(), course.clone()))
+        .collect();
+
+    sort_group_courses(group, &mut siblings);
+
+    let position = match siblings.iter().position(|(sibling, _)| sibling == url) {
+        Some(position) => position,
+        None => return (None, None),
+    };
+
+    let link = |(sibling, course): &(String, Course)| NavLink {
+        title: course.title.clone(),
+        href: course_href(base_url, &format!("{}/{}", group_name, sibling)),
+    };
+
+    (
+        position.checked_sub(1).map(|i| link(&siblings[i])),
+        siblings.get(position + 1).map(link),
+    )
+}
+
+/// Orders the courses for the home page, groups are sorted by weight then title and
+/// the courses within a group are in the order given by the group file followed by the rest sorted by weight then title.
+/// Nested groups come straight after their parent group and groups without any courses (even nested ones) are left out.
+/// Unlisted courses are left out since they can only be reached by their url.
+pub fn home_groups(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    course_index: CourseIndex,
+) -> Vec<HomeGroup> {
+    let mut course_index: CourseIndex = course_index
+        .into_iter()
+        .map(|(group_name, courses)| {
+            let listed: BTreeMap<String, Course> = courses
+                .into_iter()
+                .filter(|(_, course)| !course.unlisted)
+                .collect();
+
+            (group_name, listed)
+        })
+        .filter(|(_, courses)| !courses.is_empty())
+        .collect();
+
+    // The groups that directly contain each group (the top level groups are in "")
+    let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for group_name in course_groups.keys() {
+        let parent = group_name
+            .rfind('/')
+            .map(|i| &group_name[..i])
+            .unwrap_or("");
+        children.entry(parent).or_default().push(group_name);
+    }
+
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|name| {
+            let info = &course_groups[*name].info;
+            (info.weight, info.title.clone(), *name)
+        });
+    }
+
+    let mut home_groups = Vec::new();
+
+    // Depth first so that each group is followed by its nested groups
+    let mut stack: Vec<(&str, usize)> = children
+        .get("")
+        .map(|top_level| top_level.iter().rev().map(|name| (*name, 0)).collect())
+        .unwrap_or_default();
+
+    while let Some((group_name, depth)) = stack.pop() {
+        // Only groups which contain a course somewhere are shown
+        let prefix = format!("{}/", group_name);
+        if !course_index
+            .keys()
+            .any(|name| name == group_name || name.starts_with(&prefix))
+        {
+            continue;
+        }
+
+        let group = &course_groups[group_name];
+
+        let mut courses: Vec<(String, Course)> = course_index
+            .remove(group_name)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        sort_group_courses(group, &mut courses);
+
+        home_groups.push(HomeGroup {
+            name: group_name.to_string(),
+            anchor: group_anchor(group_name),
+            depth,
+            info: group.info.clone(),
+            courses,
+        });
+
+        if let Some(subgroups) = children.get(group_name) {
+            stack.extend(subgroups.iter().rev().map(|name| (*name, depth + 1)));
+        }
+    }
+
+    home_groups
+}
+
+/// Lists the paths in a directory sorted by name (`read_dir` doesn't guarantee any order)
+pub fn sorted_dir(folder: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = std::fs::read_dir(folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Prints the message, or returns it as an error in strict mode
+fn report_problem(msg: String, strict_mode: bool) -> Result<(), CourseError> {
+    if strict_mode {
+        Err(CourseError::Parse(msg))
+    } else {
+        println!("{}", msg);
+        Ok(())
+    }
+}
+
+/// Returns the courses in a cycle (starting and ending with the same course) if one can be reached from `reference`.
+/// `visiting` is the chain of prerequisites that led here and `done` holds the courses known not to lead to a cycle.
+fn find_prerequisite_cycle<'a>(
+    reference: &'a str,
+    courses: &BTreeMap<String, (&PathBuf, &'a Course)>,
+    visiting: &mut Vec<&'a str>,
+    done: &mut BTreeSet<&'a str>,
+) -> Option<Vec<&'a str>> {
+    if done.contains(reference) {
+        return None;
+    }
+
+    if let Some(start) = visiting.iter().position(|visited| *visited == reference) {
+        let mut cycle = visiting[start..].to_vec();
+        cycle.push(reference);
+        return Some(cycle);
+    }
+
+    visiting.push(reference);
+
+    if let Some((_, course)) = courses.get(reference) {
+        for prerequisite in &course.prerequisites {
+            if let Some(cycle) = find_prerequisite_cycle(prerequisite, courses, visiting, done) {
+                return Some(cycle);
+            }
+        }
+    }
+
+    visiting.pop();
+    done.insert(reference);
+
+    None
+}
+
+/// Checks that the prerequisites of every course exist and that no course (indirectly) requires itself.
+/// `courses` is keyed by the `group/url` of each course.
+fn check_prerequisites(
+    courses: &BTreeMap<String, (&PathBuf, &Course)>,
+    draft_urls: &BTreeSet<String>,
+    strict_mode: bool,
+) -> Result<(), CourseError> {
+    for (course_path, course) in courses.values() {
+        for prerequisite in &course.prerequisites {
+            let problem = if draft_urls.contains(prerequisite) {
+                "is a draft so it isn't built (build with --include-drafts to include it)"
+            } else if !courses.contains_key(prerequisite) {
+                "doesn't exist (prerequisites use the group and the url of the course, e.g. `python/python_introduction`)"
+            } else {
+                continue;
+            };
+
+            report_problem(
+                format!(
+                    "{} ========= Unable to parse: {:?}\nThe prerequisite `{}` {}\n",
+                    if strict_mode { "FATAL" } else { "WARNING" },
+                    course_path,
+                    prerequisite,
+                    problem
+                ),
+                strict_mode,
+            )?;
+        }
+    }
+
+    let mut done = BTreeSet::new();
+
+    for (reference, (course_path, _)) in courses {
+        if let Some(cycle) = find_prerequisite_cycle(reference, courses, &mut Vec::new(), &mut done)
+        {
+            report_problem(
+                format!(
+                    "{} ========= Unable to parse: {:?}\nThe prerequisites form a cycle so none of them can be started: {}\n",
+                    if strict_mode { "FATAL" } else { "WARNING" },
+                    course_path,
+                    cycle.join(" -> ")
+                ),
+                strict_mode,
+            )?;
+
+            // The courses in the cycle are marked as done so the same cycle isn't reported again
+            done.extend(cycle);
+        }
+    }
+
+    Ok(())
+}
+
+/// Quotes text as a JSON string, which is also a valid JavaScript string
+pub fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            // `</script>` must not appear, and JavaScript doesn't allow these line separators in strings
+            '<' | '\u{2028}' | '\u{2029}' => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+/// The course (as `group/url`) an alias refers to, aliases without a group are in the group of the course
+pub fn alias_reference(group_name: &str, alias: &str) -> String {
+    if alias.contains('/') {
+        alias.to_string()
+    } else {
+        format!("{}/{}", group_name, alias)
+    }
+}
+
+/// Maps every alias (as `group/url`) to the course (as `group/url`) it redirects to
+pub fn course_aliases(course_groups: &BTreeMap<String, CourseGroup>) -> BTreeMap<String, String> {
+    let mut aliases = BTreeMap::new();
+
+    for (group_name, group) in course_groups {
+        for (url, course_file) in &group.courses {
+            for alias in &course_file.course.aliases {
+                aliases
+                    .entry(alias_reference(group_name, alias))
+                    .or_insert_with(|| format!("{}/{}", group_name, url));
+            }
+        }
+    }
+
+    aliases
+}
+
+/// Checks that no alias is the url of a course or an alias of another course, since the redirect would replace it,
+/// and that no alias is inside the folder of a course (e.g. `python/introduction/assets`) where it could replace an asset.
+/// `courses` is keyed by the `group/url` of each course.
+fn check_aliases(
+    courses: &BTreeMap<String, (&PathBuf, &Course)>,
+    strict_mode: bool,
+) -> Result<(), CourseError> {
+    let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+
+    for (reference, (course_path, course)) in courses {
+        let group_name = &reference[..reference.rfind('/').expect("References contain the group")];
+
+        for alias in &course.aliases {
+            let alias_ref = alias_reference(group_name, alias);
+
+            // The course whose folder the redirect would be written into (if there is one)
+            let containing_course = alias_ref
+                .match_indices('/')
+                .map(|(i, _)| &alias_ref[..i])
+                .find(|prefix| courses.contains_key(*prefix));
+
+            let problem = if courses.contains_key(&alias_ref) {
+                format!("is the url of the course `{}`", alias_ref)
+            } else if let Some(other) = containing_course {
+                format!("is inside the folder of the course `{}`", other)
+            } else if let Some(other) = seen.insert(alias_ref, reference) {
+                format!("is also an alias of the course `{}`", other)
+            } else {
+                continue;
+            };
+
+            report_problem(
+                format!(
+                    "{} ========= Unable to parse: {:?}\nThe alias `{}` {}\n",
+                    if strict_mode { "FATAL" } else { "WARNING" },
+                    course_path,
+                    alias,
+                    problem
+                ),
+                strict_mode,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a map of group names to course groups (sorted by name).
+/// The name of a nested group is its path from the course folder, e.g. `languages/python/beginner`.
+/// Every group folder is included, even if it only contains other groups.
+/// Draft courses are left out unless `include_drafts` is set.
 pub fn get_courses<P: AsRef<Path>>(
     course_folder: P,
+    site_config: &SiteConfig,
     strict_mode: bool,
-) -> Result<HashMap<String, HashMap<String, PathBuf>>, CourseError> {
-    let mut course_groups = HashMap::new();
+    include_drafts: bool,
+) -> Result<BTreeMap<String, CourseGroup>, CourseError> {
+    let mut course_groups = BTreeMap::new();
+    // The drafts that were left out, keyed by the `group/name` used in links
+    let mut drafts = BTreeSet::new();
+    // The drafts that were left out, keyed by `group/url` (used by prerequisites)
+    let mut draft_urls = BTreeSet::new();
 
-    for course_group_entry in std::fs::read_dir(course_folder)? {
-        let course_group_folder = course_group_entry?.path();
+    // Group folders that still need to be scanned along with their name (the path from the course folder),
+    // groups can be nested to any depth
+    let mut group_folders: Vec<(PathBuf, String)> = Vec::new();
+
+    // Push in reverse so that the groups are scanned in order
+    for course_group_folder in sorted_dir(course_folder.as_ref())?.into_iter().rev() {
         if course_group_folder.is_dir() {
             let course_group_name = course_group_folder
                 .file_name()
@@ -59,54 +697,398 @@ pub fn get_courses<P: AsRef<Path>>(
                 .into_string()
                 .unwrap();
 
-            for course_entry in std::fs::read_dir(course_group_folder)? {
-                let course_path = course_entry?.path();
-
-                if let Some("yml") = course_path.extension().and_then(std::ffi::OsStr::to_str) {
-                    let course_str = std::fs::read_to_string(course_path.clone())
-                        .expect("Couldn't open and read course file");
-                    let course = match crate::parse::parse_course(&course_str) {
-                        Ok(c) => c,
-                        Err(err) => {
-                            let mut msg = format!(
-                                "{} ========= Unable to parse: {:?}\n",
-                                if strict_mode { "FATAL" } else { "WARNING" },
-                                &course_path
-                            );
-
-                            generate_error_message(&mut msg, &course_str, err);
-
-                            if strict_mode {
-                                // Exit the program
-                                return Err(CourseError::Parse(msg));
-                            } else {
-                                // Print the message and continue
-                                println!("{}\n", msg);
-                                continue;
-                            }
+            // The learning paths are kept in their own folder rather than being a group
+            if course_group_name == PATHS_FOLDER {
+                continue;
+            }
+
+            group_folders.push((course_group_folder, course_group_name));
+        }
+    }
+
+    while let Some((course_group_folder, course_group_name)) = group_folders.pop() {
+        let group_info = get_group_info(&course_group_folder, &course_group_name, strict_mode)?;
+
+        let mut course_group = CourseGroup {
+            path: course_group_folder.clone(),
+            info: group_info,
+            courses: BTreeMap::new(),
+        };
+
+        let mut subgroup_folders = Vec::new();
+
+        for course_path in sorted_dir(&course_group_folder)? {
+            if course_path.is_dir() {
+                // A folder with the same name as a course is the assets folder of that course, not a group
+                if !course_path.with_extension("yml").is_file() {
+                    let subgroup_name = format!(
+                        "{}/{}",
+                        course_group_name,
+                        course_path
+                            .file_name()
+                            .expect("Couldn't extract course group from folder")
+                            .to_string_lossy()
+                    );
+
+                    subgroup_folders.push((course_path, subgroup_name));
+                }
+
+                continue;
+            }
+
+            // The group file describes the group rather than being a course
+            if course_path.file_name().and_then(OsStr::to_str) == Some(GROUP_FILE) {
+                continue;
+            }
+
+            if let Some("yml") = course_path.extension().and_then(OsStr::to_str) {
+                // The file may have gone since the folder was read (e.g. an editor saving by renaming over it)
+                let course_str = std::fs::read_to_string(&course_path).map_err(|err| {
+                    std::io::Error::new(
+                        err.kind(),
+                        format!("Couldn't read the course file {:?}: {}", &course_path, err),
+                    )
+                })?;
+                let course = match crate::parse::parse_course(&course_str, site_config) {
+                    Ok(c) => c,
+                    Err(err) => {
+                        let mut msg = format!(
+                            "{} ========= Unable to parse: {:?}\n",
+                            if strict_mode { "FATAL" } else { "WARNING" },
+                            &course_path
+                        );
+
+                        generate_error_message(&mut msg, &course_str, err);
+
+                        if strict_mode {
+                            // Exit the program
+                            return Err(CourseError::Parse(msg));
+                        } else {
+                            // Print the message and continue
+                            println!("{}\n", msg);
+                            continue;
                         }
-                    };
-
-                    // We want the path up to the name excluding the .yml
-                    let path = std::path::Path::new(course_path.parent().unwrap())
-                        .join(course_path.file_stem().unwrap());
-
-                    // If a course already existed
-                    if course_groups
-                        .entry(course_group_name.clone())
-                        .or_insert_with(HashMap::new)
-                        .insert(course.url.clone(), path)
-                        .is_some()
-                    {
-                        return Err(CourseError::Parse(format!(
-                            "Two courses (in the same group) had the same url value of `{}/{}`",
-                            course_group_name, course.url
-                        )));
                     }
+                };
+
+                for warning in &course.warnings {
+                    println!("WARNING ========= {:?}\n{}\n", &course_path, warning);
+                }
+
+                if course.draft && !include_drafts {
+                    println!("Skipping the draft course {:?}", &course_path);
+                    drafts.insert(format!(
+                        "{}/{}",
+                        course_group_name,
+                        course_path.file_stem().unwrap().to_string_lossy()
+                    ));
+                    draft_urls.insert(format!("{}/{}", course_group_name, course.url));
+                    continue;
+                }
+
+                // We want the path up to the name excluding the .yml
+                let path = std::path::Path::new(course_path.parent().unwrap())
+                    .join(course_path.file_stem().unwrap());
+
+                let url = course.url.clone();
+
+                // If a course already existed
+                if course_group
+                    .courses
+                    .insert(url.clone(), CourseFile { path, course })
+                    .is_some()
+                {
+                    return Err(CourseError::Parse(format!(
+                        "Two courses (in the same group) had the same url value of `{}/{}`",
+                        course_group_name, url
+                    )));
+                }
+            }
+        }
+
+        // Subgroups are scanned next (in order) so that the scan is depth first
+        group_folders.extend(subgroup_folders.into_iter().rev());
+
+        course_groups.insert(course_group_name, course_group);
+    }
+
+    // Courses and subgroups share urls so a course can't have the same url as a subgroup
+    for (group_name, group) in &course_groups {
+        for url in group.courses.keys() {
+            if course_groups.contains_key(&format!("{}/{}", group_name, url)) {
+                return Err(CourseError::Parse(format!(
+                    "The course with the url `{}/{}` has the same url as a group folder, change the url of the course",
+                    group_name, url
+                )));
+            }
+        }
+    }
+
+    // Every course keyed by the `group/name` used in links, along with the path to its file
+    let parsed_courses: BTreeMap<String, (PathBuf, &Course)> = course_groups
+        .iter()
+        .flat_map(|(group_name, group)| {
+            group.courses.values().map(move |course_file| {
+                (
+                    format!("{}/{}", group_name, course_file.name()),
+                    (course_file.path.with_extension("yml"), &course_file.course),
+                )
+            })
+        })
+        .collect();
+
+    // The order in a group file can only refer to courses that exist
+    for (group_name, group) in &course_groups {
+        for name in &group.info.course_order {
+            let link_name = format!("{}/{}", group_name, name);
+
+            if !parsed_courses.contains_key(&link_name) && !drafts.contains(&link_name) {
+                let msg = format!(
+                    "{} ========= Unable to parse: {:?}\nThe course order refers to `{}` but there is no course file named `{}.yml` in the group\n",
+                    if strict_mode { "FATAL" } else { "WARNING" },
+                    group.path.join(GROUP_FILE),
+                    name,
+                    name
+                );
+
+                if strict_mode {
+                    return Err(CourseError::Parse(msg));
+                } else {
+                    println!("{}", msg);
+                }
+            }
+        }
+    }
+
+    // Now that every course is known check that the `course:` links point at something
+    for (course_path, course) in parsed_courses.values() {
+        for link in &course.links {
+            let (target, fragment) =
+                split_course_link(&link.href).expect("Course links always use the scheme");
+
+            let problem = if target.is_empty() && fragment.is_none() {
+                Some("it doesn't point at anything".to_string())
+            } else {
+                let target_course = if target.is_empty() {
+                    Some(course)
+                } else {
+                    parsed_courses
+                        .get(target)
+                        .map(|(_, target_course)| target_course)
+                };
+
+                match (target_course, fragment) {
+                    (None, _) if drafts.contains(target) => Some(format!(
+                        "the course `{}` is a draft so it isn't built (build with --include-drafts to include it)",
+                        target
+                    )),
+                    (None, _) => Some(format!(
+                        "there is no course `{}` (links use the group and the name of the course file, e.g. `course:python/introduction`)",
+                        target
+                    )),
+                    (Some(target_course), Some(fragment)) if !target_course.has_anchor(fragment) => {
+                        Some(format!(
+                            "the course has no tutorial or heading with the id `{}`",
+                            fragment
+                        ))
+                    }
+                    _ => None,
+                }
+            };
+
+            if let Some(problem) = problem {
+                let msg = format!(
+                    "{} ========= Broken link in: {:?}\nThe link `{}` is broken, {} in context {}\n",
+                    if strict_mode { "FATAL" } else { "WARNING" },
+                    course_path,
+                    link.href,
+                    problem,
+                    link.context
+                );
+
+                if strict_mode {
+                    return Err(CourseError::Parse(msg));
+                } else {
+                    println!("{}", msg);
                 }
             }
         }
     }
 
+    // Prerequisites use the url of the course rather than the file name
+    let courses_by_url: BTreeMap<String, (&PathBuf, &Course)> = parsed_courses
+        .iter()
+        .map(|(link_name, (course_path, course))| {
+            let group_name =
+                &link_name[..link_name.rfind('/').expect("Link names contain the group")];
+
+            (
+                format!("{}/{}", group_name, course.url),
+                (course_path, *course),
+            )
+        })
+        .collect();
+
+    check_prerequisites(&courses_by_url, &draft_urls, strict_mode)?;
+    check_aliases(&courses_by_url, strict_mode)?;
+
     Ok(course_groups)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A course with a list (e.g. its `prerequisites`) set to the values
+    fn course(key: &str, values: &[&str]) -> Course {
+        let yaml = format!(
+            "title: Test\nurl: test\n{}: [{}]\ntutorials:\n  - subtitle: Test\n    content: Test\n",
+            key,
+            values.join(", ")
+        );
+
+        crate::parse::parse_course(&yaml, &SiteConfig::default()).unwrap()
+    }
+
+    /// Finds a cycle starting from `reference`, `courses` are the `group/url` of each course and its prerequisites
+    fn cycle_from(reference: &str, courses: &[(&str, &[&str])]) -> Option<Vec<String>> {
+        let path = PathBuf::new();
+        let courses: Vec<(String, Course)> = courses
+            .iter()
+            .map(|(reference, prerequisites)| {
+                (
+                    reference.to_string(),
+                    course("prerequisites", prerequisites),
+                )
+            })
+            .collect();
+        let courses: BTreeMap<String, (&PathBuf, &Course)> = courses
+            .iter()
+            .map(|(reference, course)| (reference.clone(), (&path, course)))
+            .collect();
+
+        find_prerequisite_cycle(reference, &courses, &mut Vec::new(), &mut BTreeSet::new())
+            .map(|cycle| cycle.into_iter().map(str::to_string).collect())
+    }
+
+    #[test]
+    fn no_prerequisite_cycle() {
+        let courses: &[(&str, &[&str])] =
+            &[("g/a", &["g/b", "g/c"]), ("g/b", &["g/c"]), ("g/c", &[])];
+
+        assert_eq!(cycle_from("g/a", courses), None);
+    }
+
+    #[test]
+    fn course_requires_itself() {
+        let courses: &[(&str, &[&str])] = &[("g/a", &["g/a"])];
+
+        assert_eq!(
+            cycle_from("g/a", courses),
+            Some(vec!["g/a".to_string(), "g/a".to_string()])
+        );
+    }
+
+    #[test]
+    fn three_course_prerequisite_cycle() {
+        let courses: &[(&str, &[&str])] = &[
+            ("g/a", &["g/b"]),
+            ("g/b", &["g/c"]),
+            ("g/c", &["g/a"]),
+            ("g/d", &["g/b"]),
+        ];
+
+        assert_eq!(
+            cycle_from("g/a", courses),
+            Some(
+                vec!["g/a", "g/b", "g/c", "g/a"]
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect()
+            )
+        );
+
+        // The cycle is found from a course which leads into it, but the course itself isn't part of it
+        assert_eq!(
+            cycle_from("g/d", courses),
+            Some(
+                vec!["g/b", "g/c", "g/a", "g/b"]
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect()
+            )
+        );
+    }
+
+    /// Checks the aliases of the courses, `courses` are the `group/url` of each course and its aliases.
+    /// Returns the problem if there is one.
+    fn alias_problem(courses: &[(&str, &[&str])]) -> Option<String> {
+        let path = PathBuf::new();
+        let courses: Vec<(String, Course)> = courses
+            .iter()
+            .map(|(reference, aliases)| (reference.to_string(), course("aliases", aliases)))
+            .collect();
+        let courses: BTreeMap<String, (&PathBuf, &Course)> = courses
+            .iter()
+            .map(|(reference, course)| (reference.clone(), (&path, course)))
+            .collect();
+
+        match check_aliases(&courses, true) {
+            Ok(()) => None,
+            Err(CourseError::Parse(msg)) => Some(msg),
+            Err(CourseError::Io(err)) => panic!("Unexpected IO error: {}", err),
+        }
+    }
+
+    #[test]
+    fn alias_reference_group() {
+        assert_eq!(alias_reference("python", "old_url"), "python/old_url");
+        assert_eq!(
+            alias_reference("python/beginner", "old_url"),
+            "python/beginner/old_url"
+        );
+        assert_eq!(
+            alias_reference("python", "languages/old_url"),
+            "languages/old_url"
+        );
+    }
+
+    #[test]
+    fn valid_aliases() {
+        let courses: &[(&str, &[&str])] = &[
+            ("g/a", &["old_a", "other/a"]),
+            ("g/b", &["old_b"]),
+            // Next to a course (rather than inside its folder) is fine
+            ("g/c", &["g/a_old"]),
+        ];
+
+        assert_eq!(alias_problem(courses), None);
+    }
+
+    #[test]
+    fn alias_is_course_url() {
+        let courses: &[(&str, &[&str])] = &[("g/a", &["b"]), ("g/b", &[])];
+
+        assert!(alias_problem(courses)
+            .unwrap()
+            .contains("is the url of the course `g/b`"));
+    }
+
+    #[test]
+    fn duplicate_alias() {
+        let courses: &[(&str, &[&str])] = &[("g/a", &["old"]), ("g/b", &["g/old"])];
+
+        assert!(alias_problem(courses)
+            .unwrap()
+            .contains("is also an alias of the course `g/a`"));
+    }
+
+    #[test]
+    fn alias_inside_course_folder() {
+        let courses: &[(&str, &[&str])] = &[("g/a", &["g/b/assets"]), ("g/b", &[])];
+
+        assert!(alias_problem(courses)
+            .unwrap()
+            .contains("is inside the folder of the course `g/b`"));
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use crate::common::{generate_error_message, CourseError};
+
+/// The name of the (optional) site wide config file in the root of the course folder
+pub const CONFIG_FILE: &str = "config.yml";
+
+#[derive(Debug, Clone, Default)]
+/// Site wide settings (read from `config.yml`)
+pub struct SiteConfig {
+    /// The default markdown settings for every course (courses can override these)
+    pub markdown: MarkdownSettings,
+    /// The scheme and host the site is served from (e.g. `https://example.com`),
+    /// the sitemap is only built if this is set since it needs full urls
+    pub site_origin: Option<String>,
+    /// Whether to also build a `robots.txt` pointing to the sitemap
+    pub robots_txt: bool,
+}
+
+#[derive(Debug, Clone)]
+/// Toggles for the pulldown-cmark extensions, these all default to true
+pub struct MarkdownSettings {
+    pub tables: bool,
+    pub footnotes: bool,
+    pub strikethrough: bool,
+    pub tasklists: bool,
+}
+
+impl MarkdownSettings {
+    /// Whether the extension with the given config key is enabled (unknown extensions are not)
+    pub fn is_enabled(&self, extension: &str) -> bool {
+        match extension {
+            "tables" => self.tables,
+            "footnotes" => self.footnotes,
+            "strikethrough" => self.strikethrough,
+            "tasklists" => self.tasklists,
+            _ => false,
+        }
+    }
+}
+
+impl std::default::Default for MarkdownSettings {
+    fn default() -> Self {
+        MarkdownSettings {
+            tables: true,
+            footnotes: true,
+            strikethrough: true,
+            tasklists: true,
+        }
+    }
+}
+
+/// Reads the site config from the course folder, if there is no config file the defaults are used
+pub fn load_config<P: AsRef<Path>>(course_folder: P) -> Result<SiteConfig, CourseError> {
+    let config_path = course_folder.as_ref().join(CONFIG_FILE);
+
+    if !config_path.is_file() {
+        return Ok(SiteConfig::default());
+    }
+
+    let config_str = std::fs::read_to_string(&config_path)?;
+
+    crate::parse::parse_config(&config_str).map_err(|err| {
+        let mut msg = format!("FATAL ========= Unable to parse: {:?}\n", &config_path);
+        generate_error_message(&mut msg, &config_str, err);
+
+        CourseError::Parse(msg)
+    })
+}
@@ -0,0 +1,127 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::common::{course_href, group_anchor, CourseFile, CourseGroup};
+use crate::sitemap::{format_timestamp, last_modified};
+use crate::templates::{Feed, FeedEntry};
+
+/// The name of the feed file, the site wide feed is in the output folder
+/// and the feed of each group is in `group/{group name}/`
+pub const FEED_FILE: &str = "feed.xml";
+
+/// Used as the update time of a feed without any courses
+const EPOCH: &str = "1970-01-01T00:00:00Z";
+
+/// When the course was last updated: its `updated` date if it has one,
+/// otherwise when the course file (or any of its assets) was last modified
+fn updated(course_file: &CourseFile) -> io::Result<String> {
+    match &course_file.course.updated {
+        Some(updated) => Ok(format!("{}T00:00:00Z", updated)),
+        None => last_modified(course_file).map(format_timestamp),
+    }
+}
+
+fn feed(
+    title: String,
+    self_url: String,
+    page_url: String,
+    author: String,
+    entries: Vec<FeedEntry>,
+) -> Feed {
+    Feed {
+        title,
+        self_url,
+        page_url,
+        author,
+        // The entries are sorted so the first one is the latest
+        updated: entries
+            .first()
+            .map_or_else(|| EPOCH.to_string(), |entry| entry.updated.clone()),
+        entries,
+    }
+}
+
+/// Builds the site wide feed and the feed of every group (which also has the courses of its nested groups),
+/// returns the path of each feed in the output folder along with the feed.
+/// The courses are ordered by when they were last updated (latest first), drafts and unlisted courses are left out.
+pub fn feeds(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    site_origin: &str,
+    base_url: &str,
+) -> io::Result<Vec<(String, Feed)>> {
+    let mut entries: Vec<(&str, FeedEntry)> = Vec::new();
+
+    for (group_name, group) in course_groups {
+        for (url, course_file) in &group.courses {
+            let course = &course_file.course;
+
+            if course.draft || course.unlisted {
+                continue;
+            }
+
+            entries.push((
+                group_name,
+                FeedEntry {
+                    title: course.title.clone(),
+                    url: format!(
+                        "{}{}",
+                        site_origin,
+                        course_href(base_url, &format!("{}/{}", group_name, url))
+                    ),
+                    updated: updated(course_file)?,
+                    authors: course.authors.clone(),
+                    description: course.description.clone(),
+                },
+            ));
+        }
+    }
+
+    // Courses updated at the same time are ordered by url so that the feed is always the same
+    entries.sort_by(|(_, a), (_, b)| {
+        (Reverse(&a.updated), &a.url).cmp(&(Reverse(&b.updated), &b.url))
+    });
+
+    let home_url = format!("{}{}/index.html", site_origin, base_url);
+    // There isn't a site title so the site is named by its host, e.g. `example.com`
+    let site_name = site_origin
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+
+    let mut feeds = vec![(
+        FEED_FILE.to_string(),
+        feed(
+            "New and updated courses".to_string(),
+            format!("{}{}/{}", site_origin, base_url, FEED_FILE),
+            home_url.clone(),
+            site_name.to_string(),
+            entries.iter().map(|(_, entry)| entry.clone()).collect(),
+        ),
+    )];
+
+    for (group_name, group) in course_groups {
+        let prefix = format!("{}/", group_name);
+        let rel_path = format!("group/{}/{}", group_name, FEED_FILE);
+
+        let group_entries = entries
+            .iter()
+            .filter(|(entry_group, _)| {
+                entry_group == group_name || entry_group.starts_with(&prefix)
+            })
+            .map(|(_, entry)| entry.clone())
+            .collect();
+
+        feeds.push((
+            rel_path.clone(),
+            feed(
+                group.info.title.clone(),
+                format!("{}{}/{}", site_origin, base_url, rel_path),
+                format!("{}#{}", home_url, group_anchor(group_name)),
+                group.info.title.clone(),
+                group_entries,
+            ),
+        ));
+    }
+
+    Ok(feeds)
+}
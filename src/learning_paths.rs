@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::assets::Assets;
+use crate::common::{
+    course_href, course_nav_link, find_course, generate_error_message, sorted_dir, CourseError,
+    CourseGroup, CourseIndex,
+};
+use crate::templates::{LearningPath, NavLink, Overview, OverviewEntry, PathNav, PathPage};
+
+/// The folder (directly inside the course folder) containing the learning paths, it is also used in urls
+pub const PATHS_FOLDER: &str = "paths";
+
+/// Returns a map of the name of each learning path to the path (sorted by name).
+/// Every course in a path must exist in `course_groups`.
+pub fn get_paths<P: AsRef<Path>>(
+    course_folder: P,
+    course_groups: &BTreeMap<String, CourseGroup>,
+    strict_mode: bool,
+) -> Result<BTreeMap<String, LearningPath>, CourseError> {
+    let mut paths = BTreeMap::new();
+    let paths_folder = course_folder.as_ref().join(PATHS_FOLDER);
+
+    if !paths_folder.is_dir() {
+        return Ok(paths);
+    }
+
+    for path_file in sorted_dir(&paths_folder)? {
+        if path_file.extension().and_then(OsStr::to_str) != Some("yml") {
+            continue;
+        }
+
+        let name = path_file
+            .file_stem()
+            .expect("Couldn't extract the name of the path file")
+            .to_string_lossy()
+            .to_string();
+
+        let path_str = std::fs::read_to_string(&path_file)?;
+
+        let problem = match crate::parse::parse_path(&path_str, &name) {
+            Ok(path) => match check_path(&path, course_groups) {
+                Some(problem) => problem,
+                None => {
+                    paths.insert(name, path);
+                    continue;
+                }
+            },
+            Err(err) => {
+                let mut msg = String::new();
+                generate_error_message(&mut msg, &path_str, err);
+                msg
+            }
+        };
+
+        let msg = format!(
+            "{} ========= Unable to parse: {:?}\n{}",
+            if strict_mode { "FATAL" } else { "WARNING" },
+            &path_file,
+            problem
+        );
+
+        if strict_mode {
+            return Err(CourseError::Parse(msg));
+        } else {
+            // Print the message and leave the path out
+            println!("{}", msg);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Returns a description of the problem if the path refers to a course that doesn't exist or has a course twice
+fn check_path(
+    path: &LearningPath,
+    course_groups: &BTreeMap<String, CourseGroup>,
+) -> Option<String> {
+    let mut seen = BTreeSet::new();
+
+    for reference in &path.courses {
+        let exists = match reference.rfind('/') {
+            Some(split) => course_groups
+                .get(&reference[..split])
+                .is_some_and(|group| group.courses.contains_key(&reference[split + 1..])),
+            None => false,
+        };
+
+        if !exists {
+            return Some(format!(
+                "Error: The course `{}` doesn't exist or is a draft that was left out (paths use the group and the url of the course, e.g. `python/python_introduction`)\n",
+                reference
+            ));
+        }
+
+        if !seen.insert(reference) {
+            return Some(format!(
+                "Error: The course `{}` is in the path more than once\n",
+                reference
+            ));
+        }
+    }
+
+    None
+}
+
+/// The url of the page of a learning path
+fn path_href(base_url: &str, name: &str) -> String {
+    format!("{}/{}/{}/index.html", base_url, PATHS_FOLDER, name)
+}
+
+/// Where the course (`group/url`) is in every learning path that contains it
+pub fn path_navs(
+    reference: &str,
+    paths: &BTreeMap<String, LearningPath>,
+    course_index: &CourseIndex,
+    base_url: &str,
+) -> Vec<PathNav> {
+    paths
+        .values()
+        .filter_map(|path| {
+            let position = path.courses.iter().position(|course| course == reference)?;
+
+            Some(PathNav {
+                path: NavLink {
+                    title: path.title.clone(),
+                    href: path_href(base_url, &path.name),
+                },
+                position: position + 1,
+                total: path.courses.len(),
+                previous: position
+                    .checked_sub(1)
+                    .and_then(|i| course_nav_link(course_index, &path.courses[i], base_url)),
+                next: path
+                    .courses
+                    .get(position + 1)
+                    .and_then(|next| course_nav_link(course_index, next, base_url)),
+            })
+        })
+        .collect()
+}
+
+/// The page listing the courses in a learning path
+pub fn path_page(
+    path: &LearningPath,
+    course_index: &CourseIndex,
+    base_url: &str,
+    assets: &Assets,
+) -> PathPage {
+    PathPage {
+        base_url: base_url.to_string(),
+        assets: assets.clone(),
+        path: path.clone(),
+        courses: path
+            .courses
+            .iter()
+            .filter_map(|reference| {
+                find_course(course_index, reference)
+                    .map(|course| (course_href(base_url, reference), course.clone()))
+            })
+            .collect(),
+    }
+}
+
+/// The page listing every learning path
+pub fn paths_overview(
+    paths: &BTreeMap<String, LearningPath>,
+    base_url: &str,
+    assets: &Assets,
+) -> Overview {
+    Overview {
+        base_url: base_url.to_string(),
+        assets: assets.clone(),
+        title: "Learning paths".to_string(),
+        entries: paths
+            .values()
+            .map(|path| OverviewEntry {
+                name: path.title.clone(),
+                url: path_href(base_url, &path.name),
+                count: path.courses.len(),
+            })
+            .collect(),
+    }
+}
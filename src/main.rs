@@ -1,6 +1,14 @@
+mod assets;
 mod build;
 mod common;
+mod config;
+mod feed;
+mod learning_paths;
+mod manifest;
 mod parse;
+mod search;
+mod sitemap;
+mod taxonomy;
 mod templates;
 mod web;
 
@@ -38,7 +46,41 @@ fn main() -> std::io::Result<()> {
             "The base url should not end in a /"
         );
 
-        build::build_html(input, static_files, output, base_url.to_string())?;
+        let include_drafts = matches.is_present("include-drafts");
+
+        // Default is one thread per CPU
+        let jobs = matches
+            .value_of("jobs")
+            .map(|jobs| {
+                jobs.parse::<usize>()
+                    .expect("Couldn't parse the number of jobs")
+            })
+            .unwrap_or(0);
+
+        let options = build::BuildOptions {
+            base_url: base_url.to_string(),
+            include_drafts,
+            jobs,
+            incremental: matches.is_present("incremental"),
+            force: matches.is_present("force"),
+            dry_run: matches.is_present("dry-run"),
+            fingerprint_assets: matches.is_present("fingerprint-assets"),
+        };
+
+        let result = if matches.is_present("watch") {
+            build::watch(input, static_files, output, &options)
+        } else {
+            build::build_html(input, static_files, output, &options)
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(common::CourseError::Io(err)) => return Err(err),
+            Err(common::CourseError::Parse(msg)) => {
+                println!("Build process failed:\n{}", msg);
+                std::process::exit(1);
+            }
+        }
     } else if let Some(matches) = matches.subcommand_matches("start-test-server") {
         let input = matches.value_of("input-dir").unwrap();
         let static_files = matches.value_of("static-dir").unwrap();
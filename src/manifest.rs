@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::common::is_contained_path;
+
+/// The name of the file in the output folder listing what was built (used by incremental builds).
+/// It also marks the folder as the output of a build, so that it is safe to delete.
+pub const MANIFEST_FILE: &str = ".tutorial_web_manifest";
+
+/// The first line of the manifest, the version is increased whenever the format changes
+const MANIFEST_HEADER: &str = "tutorial_web build manifest v2";
+
+/// A file in the output folder (relative to the output folder, using `/`) and the hash of what it was built from
+pub type ManifestEntry = (String, u64);
+
+/// Hashes some bytes with 64 bit FNV-1a, the hashes are kept in the manifest and used in the names of
+/// fingerprinted files so the algorithm must never change (unlike the standard library's hasher)
+pub fn hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Adds a field to the input of a hash, each field is prefixed by its length
+/// so that moving text from one field to the next changes the input
+pub fn push_field(input: &mut Vec<u8>, field: &[u8]) {
+    input.extend_from_slice(&(field.len() as u64).to_le_bytes());
+    input.extend_from_slice(field);
+}
+
+/// Every file in the folder (and any nested folders) along with its path relative to the folder (using `/`),
+/// sorted by path so that the output (and the errors if any) are always the same
+pub fn relative_files(folder: &Path) -> io::Result<Vec<(PathBuf, String)>> {
+    use walkdir::WalkDir;
+
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let path = entry?.path().to_owned();
+
+        if !path.is_file() {
+            continue;
+        }
+
+        let rel_path = path
+            .strip_prefix(folder)
+            .expect("Couldn't get relative path")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        files.push((path, rel_path));
+    }
+
+    Ok(files)
+}
+
+/// Every file written by a build along with the hash of what it was built from
+#[derive(Debug, Default)]
+pub struct Manifest {
+    files: BTreeMap<String, u64>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a previous build, returns None if there isn't one or it can't be read
+    pub fn load(output: &Path) -> Option<Manifest> {
+        let manifest_str = fs::read_to_string(output.join(MANIFEST_FILE)).ok()?;
+        let mut lines = manifest_str.lines();
+
+        if lines.next() != Some(MANIFEST_HEADER) {
+            return None;
+        }
+
+        let mut files = BTreeMap::new();
+
+        for line in lines {
+            let split = line.find(' ')?;
+            let hash = u64::from_str_radix(&line[..split], 16).ok()?;
+            let path = &line[split + 1..];
+
+            // Stale files are deleted so a manifest that was edited by hand mustn't point outside the output folder
+            if !is_contained_path(path) {
+                return None;
+            }
+
+            files.insert(path.to_string(), hash);
+        }
+
+        Some(Manifest { files })
+    }
+
+    pub fn save(&self, output: &Path) -> io::Result<()> {
+        let mut file = fs::File::create(output.join(MANIFEST_FILE))?;
+
+        writeln!(file, "{}", MANIFEST_HEADER)?;
+
+        for (path, hash) in &self.files {
+            writeln!(file, "{:016x} {}", hash, path)?;
+        }
+
+        Ok(())
+    }
+
+    /// The number of files in the manifest
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn extend<I: IntoIterator<Item = ManifestEntry>>(&mut self, entries: I) {
+        self.files.extend(entries);
+    }
+
+    /// The files listed in the previous manifest that weren't built this time
+    pub fn stale_files<'b>(&self, previous: &'b Manifest) -> Vec<&'b String> {
+        previous
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .collect()
+    }
+
+    /// Deletes the files listed in the previous manifest that weren't built this time
+    /// (along with any folders that are left empty), returns how many were deleted
+    pub fn remove_stale(&self, previous: &Manifest, output: &Path) -> io::Result<usize> {
+        let mut removed = 0;
+
+        for path in self.stale_files(previous) {
+            let file = output.join(path);
+
+            match fs::remove_file(&file) {
+                Ok(()) => removed += 1,
+                Err(ref err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+
+            // Stops at the first folder which still contains something
+            let mut folder = file.parent();
+            while let Some(dir) = folder {
+                if dir == output || fs::remove_dir(dir).is_err() {
+                    break;
+                }
+
+                folder = dir.parent();
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Writes files into the output folder, skipping any which the previous manifest shows are up to date
+pub struct OutputDir<'a> {
+    root: &'a Path,
+    previous: Option<&'a Manifest>,
+    /// Only record which files would be written, without touching the output folder
+    dry_run: bool,
+    /// The files that were actually written (or would have been in a dry run)
+    written: Mutex<Vec<String>>,
+}
+
+impl<'a> OutputDir<'a> {
+    pub fn new(root: &'a Path, previous: Option<&'a Manifest>, dry_run: bool) -> OutputDir<'a> {
+        OutputDir {
+            root,
+            previous,
+            dry_run,
+            written: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The files that were written, sorted by path
+    pub fn written(&self) -> Vec<String> {
+        let mut written = self
+            .written
+            .lock()
+            .expect("A build thread panicked")
+            .clone();
+        written.sort();
+        written
+    }
+
+    /// Records that a file was written, returns false if it shouldn't actually be written (in a dry run)
+    fn record_write(&self, rel_path: &str) -> bool {
+        self.written
+            .lock()
+            .expect("A build thread panicked")
+            .push(rel_path.to_string());
+
+        !self.dry_run
+    }
+
+    /// Whether the file was built from something with the same hash last time (and still exists)
+    pub fn is_fresh(&self, rel_path: &str, hash: u64) -> bool {
+        self.previous
+            .and_then(|previous| previous.files.get(rel_path))
+            == Some(&hash)
+            && self.root.join(rel_path).is_file()
+    }
+
+    /// Writes a file built from something with the given hash,
+    /// `contents` is only called if the file is out of date
+    pub fn write_with<F>(&self, rel_path: &str, hash: u64, contents: F) -> io::Result<ManifestEntry>
+    where
+        F: FnOnce() -> io::Result<String>,
+    {
+        if !self.is_fresh(rel_path, hash) && self.record_write(rel_path) {
+            let path = self.root.join(rel_path);
+
+            fs::create_dir_all(
+                path.parent()
+                    .expect("Output files are inside the output folder"),
+            )?;
+            fs::write(path, contents()?)?;
+        }
+
+        Ok((rel_path.to_string(), hash))
+    }
+
+    /// Writes a generated file, it is only written if the contents changed
+    pub fn write(&self, rel_path: &str, contents: String) -> io::Result<ManifestEntry> {
+        self.write_with(rel_path, hash(contents.as_bytes()), || Ok(contents))
+    }
+
+    /// Copies a file into the output folder if its contents changed
+    pub fn copy(&self, source: &Path, rel_path: &str) -> io::Result<ManifestEntry> {
+        let hash = hash(&fs::read(source)?);
+
+        if !self.is_fresh(rel_path, hash) && self.record_write(rel_path) {
+            let path = self.root.join(rel_path);
+
+            fs::create_dir_all(
+                path.parent()
+                    .expect("Output files are inside the output folder"),
+            )?;
+            fs::copy(source, path)?;
+        }
+
+        Ok((rel_path.to_string(), hash))
+    }
+
+    /// Copies the files in the input folder (and any nested folders) which changed into `rel_dir`
+    pub fn copy_dir(&self, input: &Path, rel_dir: &str) -> io::Result<Vec<ManifestEntry>> {
+        relative_files(input)?
+            .into_iter()
+            .map(|(path, rel_path)| self.copy(&path, &format!("{}/{}", rel_dir, rel_path)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_fnv_1a() {
+        // Test vectors from the FNV reference implementation
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -1,10 +1,17 @@
-use crate::templates::{Course, CourseTutorialSettings, Tutorial};
+use crate::common::{is_contained_path, slugify};
+use crate::config::{MarkdownSettings, SiteConfig};
+use crate::templates::{
+    Course, CourseTutorialSettings, GroupInfo, LearningPath, QuizOption, QuizQuestion, Tutorial,
+};
 
 // use serde_yaml::Value;
 use yaml_rust::{yaml::Hash, ScanError, Yaml, YamlLoader};
 
 #[macro_use]
 mod macros;
+mod markdown;
+
+use markdown::MarkdownRenderer;
 
 /// Represents the errors that could occur parsing a YAML string into a course struct
 #[derive(Clone, Debug)]
@@ -17,6 +24,8 @@ pub enum ParseError {
     MissingRequiredKey(String, String),
     /// An unexpected or invalid (wrong type) key (in a context)
     InvalidKey(String, String),
+    /// The value associated with a key was the right type but not an allowed value (in a context)
+    InvalidValue(String, String),
 }
 
 impl std::fmt::Display for ParseError {
@@ -31,7 +40,8 @@ impl std::fmt::Display for ParseError {
             }
             InvalidType(msg, context)
             | InvalidKey(msg, context)
-            | MissingRequiredKey(msg, context) => write!(f, "{} in context {}", msg, context),
+            | MissingRequiredKey(msg, context)
+            | InvalidValue(msg, context) => write!(f, "{} in context {}", msg, context),
         }
     }
 }
@@ -63,9 +73,133 @@ fn check_keys(hash: &Hash, allowed_keys: &[&str], context: &str) -> Result<(), P
     Ok(())
 }
 
-pub fn parse_course(course: &str) -> Result<Course, ParseError> {
+/// Parses the list of questions that make up a tutorial's quiz
+fn parse_quiz(
+    questions: &[Yaml],
+    renderer: &mut MarkdownRenderer,
+    context: &str,
+) -> Result<Vec<QuizQuestion>, ParseError> {
+    use itertools::process_results;
+
+    process_results(
+        questions.iter().enumerate().map(|(i, question_value)| {
+            // Define the current context for error messages
+            let context = &format!("{}, quiz question number `{}`", context, i + 1);
+
+            let hash = match question_value.as_hash() {
+                Some(hash) => hash,
+                None => {
+                    return Err(ParseError::InvalidType(format!(
+                        "Expected all of the elements of the quiz array to be a hash, instead found {:?}",
+                        question_value
+                    ), context.to_string()))
+                }
+            };
+
+            // Check for unrecognised keys
+            check_keys(
+                hash,
+                &["question", "options", "answer", "explanation"],
+                context,
+            )?;
+
+            let question = renderer.render(yaml_str!(require: hash, question, context), context)?;
+            let options = yaml_vec!(require: hash, options, context);
+            let explanation = yaml_str!(hash, explanation, context)
+                .map(|explanation| renderer.render(explanation, context))
+                .transpose()?;
+
+            if options.len() < 2 {
+                return Err(ParseError::InvalidValue(
+                    "A quiz question must have at least two options".to_owned(),
+                    context.to_string(),
+                ));
+            }
+
+            // The answer can either be a single option number or a list of them (for questions with multiple correct answers)
+            let answer_values = match hash.get(&Yaml::String("answer".to_string())) {
+                Some(Yaml::Array(answers)) => answers.clone(),
+                Some(answer) => vec![answer.clone()],
+                None => {
+                    return Err(ParseError::MissingRequiredKey(
+                        "Missing required key `answer`".to_owned(),
+                        context.to_string(),
+                    ))
+                }
+            };
+
+            let mut answers = Vec::new();
+
+            for answer_value in answer_values {
+                let answer = match answer_value.as_i64() {
+                    Some(answer) => answer,
+                    None => {
+                        return Err(ParseError::InvalidType(format!(
+                            "Expected the value of key `answer` to be an option number or a list of option numbers, instead it contained {:?}",
+                            answer_value
+                        ), context.to_string()))
+                    }
+                };
+
+                if answer < 1 || answer as usize > options.len() {
+                    return Err(ParseError::InvalidValue(
+                        format!(
+                            "The answer `{}` does not refer to an option (options are numbered 1 to {})",
+                            answer,
+                            options.len()
+                        ),
+                        context.to_string(),
+                    ));
+                }
+
+                if answers.contains(&(answer as usize)) {
+                    return Err(ParseError::InvalidValue(
+                        format!("The answer `{}` was given more than once", answer),
+                        context.to_string(),
+                    ));
+                }
+
+                answers.push(answer as usize);
+            }
+
+            if answers.is_empty() {
+                return Err(ParseError::InvalidValue(
+                    "A quiz question must have at least one correct answer".to_owned(),
+                    context.to_string(),
+                ));
+            }
+
+            let options = process_results(
+                options.iter().enumerate().map(|(i, option_value)| {
+                    match option_value.as_str() {
+                        Some(option) => Ok(QuizOption {
+                            content: renderer.render(option, context)?,
+                            correct: answers.contains(&(i + 1)),
+                        }),
+                        None => Err(ParseError::InvalidType(format!(
+                            "Expected all of the elements of the options array to be a string (text), instead found {:?}",
+                            option_value
+                        ), context.to_string())),
+                    }
+                }),
+                |iter| iter.collect(),
+            )?;
+
+            Ok(QuizQuestion {
+                question,
+                options,
+                explanation,
+                multiple_answers: answers.len() > 1,
+            })
+        }),
+        |iter| iter.collect(),
+    )
+}
+
+/// Loads a YAML string which should contain exactly one document that is a mapping (key => value)
+fn parse_document(document: &str) -> Result<Hash, ParseError> {
     // TODO get location of error
-    let mut parsed = YamlLoader::load_from_str(course).map_err(|scan_err| {
+    let mut parsed = YamlLoader::load_from_str(document).map_err(|scan_err| {
         ParseError::YamlError("File was not valid YAML".to_owned(), Some(scan_err))
     })?;
 
@@ -85,12 +219,232 @@ pub fn parse_course(course: &str) -> Result<Course, ParseError> {
         ));
     }
 
-    let hash = parsed.remove(0).into_hash().ok_or_else(|| {
+    parsed.remove(0).into_hash().ok_or_else(|| {
         ParseError::YamlError(
             "YAML file was not a mapping (key => value).".to_owned(),
             None,
         )
-    })?;
+    })
+}
+
+/// Parses a `markdown` settings hash, any keys that are missing keep the value from `defaults`
+fn parse_markdown_settings(
+    hash: &Hash,
+    defaults: &MarkdownSettings,
+    context: &str,
+) -> Result<MarkdownSettings, ParseError> {
+    // Check for unrecognised keys
+    check_keys(
+        hash,
+        &["tables", "footnotes", "strikethrough", "tasklists"],
+        context,
+    )?;
+
+    Ok(MarkdownSettings {
+        tables: yaml_bool!(hash, tables, context).unwrap_or(defaults.tables),
+        footnotes: yaml_bool!(hash, footnotes, context).unwrap_or(defaults.footnotes),
+        strikethrough: yaml_bool!(hash, strikethrough, context).unwrap_or(defaults.strikethrough),
+        tasklists: yaml_bool!(hash, tasklists, context).unwrap_or(defaults.tasklists),
+    })
+}
+
+/// The allowed values of the `difficulty` key of a course
+pub const DIFFICULTIES: &[&str] = &["beginner", "intermediate", "advanced"];
+
+/// Parses an array where every element should be a non empty string, `name` is the key of the array
+fn parse_string_list(
+    values: &[Yaml],
+    name: &str,
+    context: &str,
+) -> Result<Vec<String>, ParseError> {
+    use itertools::process_results;
+
+    process_results(
+        values.iter().map(|value| match value.as_str() {
+            Some(string) if !string.trim().is_empty() => Ok(string.trim().to_string()),
+            _ => Err(ParseError::InvalidType(
+                format!(
+                    "Expected all of the elements of the {} array to be a non empty string (text), instead found {:?}",
+                    name, value
+                ),
+                context.to_string(),
+            )),
+        }),
+        |iter| iter.collect(),
+    )
+}
+
+/// Whether the string is a valid date in the format YYYY-MM-DD
+fn is_valid_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+
+    if parts.len() != 3
+        || parts[0].len() != 4
+        || parts[1].len() != 2
+        || parts[2].len() != 2
+        || !parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return false;
+    }
+
+    let year: u32 = parts[0].parse().unwrap();
+    let month: u32 = parts[1].parse().unwrap();
+    let day: u32 = parts[2].parse().unwrap();
+
+    let is_leap_year =
+        year.is_multiple_of(4) && !year.is_multiple_of(100) || year.is_multiple_of(400);
+    let days_in_month = match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+
+    (1..=12).contains(&month) && (1..=days_in_month).contains(&day)
+}
+
+/// Parses a learning path file, `name` is the name of the file (excluding the .yml)
+pub fn parse_path(path: &str, name: &str) -> Result<LearningPath, ParseError> {
+    let hash = parse_document(path)?;
+
+    // Define the current context for error messages
+    let context = "root level";
+
+    // Check for unrecognised keys
+    check_keys(&hash, &["title", "description", "courses"], context)?;
+
+    let title = yaml_str!(require: hash, title, context).to_string();
+    let description = yaml_str!(hash, description, context).map(str::to_string);
+    let courses = parse_string_list(
+        yaml_vec!(require: hash, courses, context),
+        "courses",
+        context,
+    )?;
+
+    if courses.is_empty() {
+        return Err(ParseError::InvalidValue(
+            "A learning path must contain at least one course".to_string(),
+            context.to_string(),
+        ));
+    }
+
+    Ok(LearningPath {
+        name: name.to_string(),
+        title,
+        description,
+        courses,
+    })
+}
+
+/// Parses the site wide config file
+pub fn parse_config(config: &str) -> Result<SiteConfig, ParseError> {
+    let hash = parse_document(config)?;
+
+    // Define the current context for error messages
+    let context = "root level";
+
+    // Check for unrecognised keys
+    check_keys(&hash, &["markdown", "site_origin", "robots_txt"], context)?;
+
+    let mut site_config = SiteConfig::default();
+
+    if let Some(markdown_hash) = yaml_hash!(hash, markdown, context) {
+        site_config.markdown =
+            parse_markdown_settings(markdown_hash, &site_config.markdown, "markdown")?;
+    }
+
+    if let Some(site_origin) = yaml_str!(hash, site_origin, context) {
+        let host = site_origin
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+
+        if host.len() == site_origin.len() || host.is_empty() || host.contains('/') {
+            return Err(ParseError::InvalidValue(
+                format!(
+                    "The site origin `{}` should only be the scheme and the host, e.g. `https://example.com` (without a trailing /, the base url is added to it)",
+                    site_origin
+                ),
+                context.to_string(),
+            ));
+        }
+
+        site_config.site_origin = Some(site_origin.to_string());
+    }
+
+    site_config.robots_txt = yaml_bool!(hash, robots_txt, context).unwrap_or(false);
+
+    if site_config.robots_txt && site_config.site_origin.is_none() {
+        return Err(ParseError::InvalidValue(
+            "`robots_txt` needs `site_origin` to be set (robots.txt points to the sitemap)"
+                .to_string(),
+            context.to_string(),
+        ));
+    }
+
+    Ok(site_config)
+}
+
+/// Parses the (optional) `_group.yml` file of a course group, `name` is the name of the group folder
+pub fn parse_group(group: &str, name: &str) -> Result<GroupInfo, ParseError> {
+    use itertools::process_results;
+
+    let hash = parse_document(group)?;
+
+    // Define the current context for error messages
+    let context = "root level";
+
+    // Check for unrecognised keys
+    check_keys(
+        &hash,
+        &["title", "description", "image", "weight", "courses"],
+        context,
+    )?;
+
+    let mut info = GroupInfo::new(name);
+
+    if let Some(title) = yaml_str!(hash, title, context) {
+        info.title = title.to_string();
+    }
+
+    info.description = yaml_str!(hash, description, context).map(str::to_string);
+    info.image = yaml_str!(hash, image, context).map(str::to_string);
+
+    // The image is copied to (and served from) this path inside the group folder
+    if let Some(image) = info
+        .image
+        .as_ref()
+        .filter(|image| !is_contained_path(image))
+    {
+        return Err(ParseError::InvalidValue(
+            format!(
+                "The image `{}` should be a path relative to the group folder (e.g. `logo.png` or `images/logo.png`)",
+                image
+            ),
+            context.to_string(),
+        ));
+    }
+    info.weight = yaml_int!(hash, weight, context).unwrap_or(info.weight);
+
+    if let Some(courses) = yaml_vec!(hash, courses, context) {
+        info.course_order = process_results(
+            courses.iter().map(|course_value| match course_value.as_str() {
+                Some(course) => Ok(course.to_string()),
+                None => Err(ParseError::InvalidType(format!(
+                    "Expected all of the elements of the courses array to be a string (the name of a course file), instead found {:?}",
+                    course_value
+                ), context.to_string())),
+            }),
+            |iter| iter.collect(),
+        )?;
+    }
+
+    Ok(info)
+}
+
+pub fn parse_course(course: &str, site_config: &SiteConfig) -> Result<Course, ParseError> {
+    let hash = parse_document(course)?;
 
     // Define the current context for error messages
     let context = "root level";
@@ -98,7 +452,25 @@ pub fn parse_course(course: &str) -> Result<Course, ParseError> {
     // Check for unrecognised keys
     check_keys(
         &hash,
-        &["title", "lang", "url", "tutorials", "tutorial_settings"],
+        &[
+            "title",
+            "lang",
+            "url",
+            "weight",
+            "description",
+            "authors",
+            "tags",
+            "difficulty",
+            "estimated_minutes",
+            "updated",
+            "draft",
+            "unlisted",
+            "prerequisites",
+            "aliases",
+            "redirect_from",
+            "tutorials",
+            "tutorial_settings",
+        ],
         context,
     )?;
 
@@ -108,8 +480,137 @@ pub fn parse_course(course: &str) -> Result<Course, ParseError> {
     let url: String = yaml_str!(require: hash, url, context).to_string();
 
     let lang: String = yaml_str!(hash, lang, context).unwrap_or("").to_string();
+    let weight = yaml_int!(hash, weight, context).unwrap_or(0);
+
+    let description = yaml_str!(hash, description, context).map(str::to_string);
+
+    let authors = match yaml_vec!(hash, authors, context) {
+        Some(authors) => parse_string_list(authors, "authors", context)?,
+        None => Vec::new(),
+    };
+
+    let tags = match yaml_vec!(hash, tags, context) {
+        Some(tags) => parse_string_list(tags, "tags", context)?,
+        None => Vec::new(),
+    };
+
+    let difficulty = match yaml_str!(hash, difficulty, context) {
+        Some(difficulty) if !DIFFICULTIES.contains(&difficulty) => {
+            return Err(ParseError::InvalidValue(
+                format!(
+                    "Unknown difficulty `{}`, expected one of {:?}",
+                    difficulty, DIFFICULTIES
+                ),
+                context.to_string(),
+            ));
+        }
+        difficulty => difficulty.map(str::to_string),
+    };
+
+    let estimated_minutes = match yaml_int!(hash, estimated_minutes, context) {
+        Some(minutes) if minutes <= 0 || minutes > i64::from(u32::MAX) => {
+            return Err(ParseError::InvalidValue(
+                format!(
+                    "The value of key `estimated_minutes` must be a positive number of minutes, instead it was {}",
+                    minutes
+                ),
+                context.to_string(),
+            ));
+        }
+        minutes => minutes.map(|minutes| minutes as u32),
+    };
+
+    let updated = match yaml_str!(hash, updated, context) {
+        Some(updated) if !is_valid_date(updated) => {
+            return Err(ParseError::InvalidValue(
+                format!(
+                    "The value of key `updated` must be a date in the format YYYY-MM-DD, instead it was `{}`",
+                    updated
+                ),
+                context.to_string(),
+            ));
+        }
+        updated => updated.map(str::to_string),
+    };
+
+    let prerequisites = match yaml_vec!(hash, prerequisites, context) {
+        Some(prerequisites) => parse_string_list(prerequisites, "prerequisites", context)?,
+        None => Vec::new(),
+    };
+
+    // `redirect_from` is accepted as another name for `aliases`
+    let mut aliases = Vec::new();
+
+    if let Some(values) = yaml_vec!(hash, aliases, context) {
+        aliases.extend(parse_string_list(values, "aliases", context)?);
+    }
+
+    if let Some(values) = yaml_vec!(hash, redirect_from, context) {
+        aliases.extend(parse_string_list(values, "redirect_from", context)?);
+    }
+
+    // The redirects are written at these paths so they must stay inside the output folder
+    if let Some(alias) = aliases.iter().find(|alias| !is_contained_path(alias)) {
+        return Err(ParseError::InvalidValue(
+            format!(
+                "The alias `{}` should be an old url of the course (e.g. `old_url`) or a group and an old url (e.g. `python/old_url`)",
+                alias
+            ),
+            context.to_string(),
+        ));
+    }
+
+    let draft = yaml_bool!(hash, draft, context).unwrap_or(false);
+    let unlisted = yaml_bool!(hash, unlisted, context).unwrap_or(false);
+
+    let tutorial_settings = match yaml_hash!(hash, tutorial_settings, context) {
+        Some(settings_hash) => {
+            // Define the current context for error messages
+            let context = "tutorial_settings";
+
+            let mut settings = CourseTutorialSettings {
+                markdown: site_config.markdown.clone(),
+                ..CourseTutorialSettings::default()
+            };
+
+            // Check for unrecognised keys
+            check_keys(
+                settings_hash,
+                &[
+                    "start_closed",
+                    "show_number",
+                    "include_solutions",
+                    "markdown",
+                ],
+                context,
+            )?;
+
+            settings.start_closed =
+                yaml_bool!(settings_hash, start_closed, context).unwrap_or(settings.start_closed);
+            settings.show_number =
+                yaml_bool!(settings_hash, show_number, context).unwrap_or(settings.show_number);
+            settings.include_solutions = yaml_bool!(settings_hash, include_solutions, context)
+                .unwrap_or(settings.include_solutions);
+
+            if let Some(markdown_hash) = yaml_hash!(settings_hash, markdown, context) {
+                settings.markdown = parse_markdown_settings(
+                    markdown_hash,
+                    &settings.markdown,
+                    "tutorial_settings.markdown",
+                )?;
+            }
 
-    let tutorials: Vec<Tutorial> = process_results(
+            settings
+        }
+        None => CourseTutorialSettings {
+            markdown: site_config.markdown.clone(),
+            ..CourseTutorialSettings::default()
+        },
+    };
+
+    let mut renderer = MarkdownRenderer::new(&tutorial_settings.markdown);
+
+    let mut tutorials: Vec<Tutorial> = process_results(
         yaml_vec!(require: hash, tutorials, context)
             .iter()
             .enumerate()
@@ -130,57 +631,155 @@ pub fn parse_course(course: &str) -> Result<Course, ParseError> {
                 // Check for unrecognised keys
                 check_keys(
                     &hash,
-                    &["subtitle", "content", "start_closed", "show_number"],
+                    &["id", "subtitle", "content", "start_closed", "show_number", "quiz", "hints", "solution"],
                     context
                 )?;
 
                 let subtitle = yaml_str!(require: hash, subtitle, context).to_string();
+
+                // Links and saved progress refer to the id so that they survive tutorials being reordered
+                let id = match yaml_str!(hash, id, context) {
+                    Some(id) => {
+                        if id.is_empty()
+                            || !id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+                        {
+                            return Err(ParseError::InvalidValue(
+                                format!(
+                                    "The id `{}` must only contain letters, numbers, `-` and `_`",
+                                    id
+                                ),
+                                context.to_string(),
+                            ));
+                        }
+
+                        id.to_string()
+                    }
+                    None => slugify(&subtitle),
+                };
+
+                if !renderer.claim_anchor(&id) {
+                    return Err(ParseError::InvalidValue(
+                        format!(
+                            "The id `{}` is already used by another tutorial (or a heading), set the `id` key to something unique",
+                            id
+                        ),
+                        context.to_string(),
+                    ));
+                }
+
+                // Now that the id is known use it for error messages
+                let context = &format!("tutorial `{}` (number {})", id, i + 1);
+
                 let markdown = yaml_str!(require: hash, content, context);
 
                 let start_closed = yaml_bool!(hash, start_closed, context);
                 let show_number = yaml_bool!(hash, show_number, context);
 
-                // Parse markdown
-                let parser = pulldown_cmark::Parser::new(&markdown);
+                renderer.anchor_prefix = id.clone();
+
+                let (content, headings) = renderer.render_with_headings(markdown, context)?;
+
+                let quiz = match yaml_vec!(hash, quiz, context) {
+                    Some(questions) => parse_quiz(questions, &mut renderer, context)?,
+                    None => Vec::new(),
+                };
+
+                let hints = match yaml_vec!(hash, hints, context) {
+                    Some(hints) => process_results(
+                        hints.iter().map(|hint_value| match hint_value.as_str() {
+                            Some(hint) => renderer.render(hint, context),
+                            None => Err(ParseError::InvalidType(format!(
+                                "Expected all of the elements of the hints array to be a string (text), instead found {:?}",
+                                hint_value
+                            ), context.to_string())),
+                        }),
+                        |iter| iter.collect(),
+                    )?,
+                    None => Vec::new(),
+                };
 
-                let mut html = String::new();
-                pulldown_cmark::html::push_html(&mut html, parser);
+                let solution = yaml_str!(hash, solution, context)
+                    .map(|solution| renderer.render(solution, context))
+                    .transpose()?;
 
                 Ok(Tutorial {
+                    id,
                     subtitle,
-                    content: html,
+                    content,
+                    headings,
                     start_closed,
-                    show_number
+                    show_number,
+                    quiz,
+                    hints,
+                    solution,
                 })
             }),
         |iter| iter.collect(),
     )?;
 
-    let tutorial_settings = match yaml_hash!(hash, tutorial_settings, context) {
-        Some(settings_hash) => {
-            // Define the current context for error messages
-            let context = "tutorial_settings";
-
-            let mut settings = CourseTutorialSettings::default();
-
-            // Check for unrecognised keys
-            check_keys(&settings_hash, &["start_closed", "show_number"], context)?;
-
-            settings.start_closed =
-                yaml_bool!(settings_hash, start_closed, context).unwrap_or(settings.start_closed);
-            settings.show_number =
-                yaml_bool!(settings_hash, show_number, context).unwrap_or(settings.show_number);
-
-            settings
+    // Solutions are dropped here so that they never make it into the output (e.g. for exam variants of a course)
+    if !tutorial_settings.include_solutions {
+        for tutorial in &mut tutorials {
+            tutorial.solution = None;
         }
-        None => CourseTutorialSettings::default(),
-    };
+    }
+
+    let warnings = renderer.warnings;
+    let links = renderer.links;
 
     Ok(Course {
         title,
         lang,
         url,
+        weight,
+        description,
+        authors,
+        tags,
+        difficulty,
+        estimated_minutes,
+        updated,
+        draft,
+        unlisted,
+        prerequisites,
+        aliases,
         tutorials,
         tutorial_settings,
+        warnings,
+        links,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_dates() {
+        assert!(is_valid_date("1970-01-01"));
+        assert!(is_valid_date("2019-12-31"));
+        assert!(is_valid_date("2019-04-30"));
+    }
+
+    #[test]
+    fn leap_days() {
+        assert!(is_valid_date("2020-02-29"));
+        assert!(is_valid_date("2000-02-29"));
+        assert!(!is_valid_date("2019-02-29"));
+        // Divisible by 100 but not 400
+        assert!(!is_valid_date("1900-02-29"));
+        assert!(!is_valid_date("2020-02-30"));
+    }
+
+    #[test]
+    fn invalid_dates() {
+        assert!(!is_valid_date("2019-04-31"));
+        assert!(!is_valid_date("2019-00-10"));
+        assert!(!is_valid_date("2019-13-01"));
+        assert!(!is_valid_date("2019-01-00"));
+        assert!(!is_valid_date("2019-1-01"));
+        assert!(!is_valid_date("19-01-01"));
+        assert!(!is_valid_date("2019-01-01T00:00"));
+        assert!(!is_valid_date("2019/01/01"));
+        assert!(!is_valid_date("+019-01-01"));
+    }
+}
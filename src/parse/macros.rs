@@ -40,3 +40,4 @@ yaml_macro!(yaml_vec, as_vec, "an array (list)");
 yaml_macro!(yaml_str, as_str, "a string (text)");
 yaml_macro!(yaml_bool, as_bool, "a boolean (true or false)");
 yaml_macro!(yaml_hash, as_hash, "a hash (key => value)");
+yaml_macro!(yaml_int, as_i64, "an integer (whole number)");
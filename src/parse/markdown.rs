@@ -0,0 +1,293 @@
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use super::ParseError;
+use crate::common::{escape_html, slugify, COURSE_LINK_SCHEME};
+use crate::config::MarkdownSettings;
+use crate::templates::{CourseLink, Heading};
+
+/// The kinds of callout that can be used in a block quote, e.g. `> [!WARNING]`
+const CALLOUT_KINDS: &[&str] = &["note", "tip", "warning", "danger"];
+
+/// The markdown extensions that can be toggled, along with the key used to configure them
+const EXTENSIONS: &[(&str, Options)] = &[
+    ("tables", Options::ENABLE_TABLES),
+    ("footnotes", Options::ENABLE_FOOTNOTES),
+    ("strikethrough", Options::ENABLE_STRIKETHROUGH),
+    ("tasklists", Options::ENABLE_TASKLISTS),
+];
+
+/// Converts the settings into the options for the pulldown-cmark parser
+fn extension_options(settings: &MarkdownSettings) -> Options {
+    let mut options = Options::empty();
+
+    for (name, option) in EXTENSIONS {
+        if settings.is_enabled(name) {
+            options.insert(*option);
+        }
+    }
+
+    options
+}
+
+/// If the events (which should directly follow the start of a block quote) begin with a
+/// callout marker such as `[!WARNING]` on its own line, this returns the marker text
+/// and the number of text events that make it up.
+fn find_callout_marker(events: &[Event]) -> Option<(String, usize)> {
+    if events.first() != Some(&Event::Start(Tag::Paragraph)) {
+        return None;
+    }
+
+    // The marker may be split over multiple text events (brackets are parsed separately)
+    let mut marker = String::new();
+    let mut text_events = 0;
+
+    for event in &events[1..] {
+        match event {
+            Event::Text(text) => {
+                marker.push_str(text);
+                text_events += 1;
+            }
+            Event::SoftBreak | Event::HardBreak | Event::End(Tag::Paragraph) => break,
+            _ => return None,
+        }
+    }
+
+    let marker = marker.trim();
+
+    if marker.starts_with("[!") && marker.ends_with(']') {
+        Some((marker[2..marker.len() - 1].to_string(), text_events))
+    } else {
+        None
+    }
+}
+
+/// Renders the markdown of a course into HTML, collecting any warnings along the way
+pub struct MarkdownRenderer<'a> {
+    settings: &'a MarkdownSettings,
+    /// Non fatal problems with the markdown (e.g. syntax for a disabled extension)
+    pub warnings: Vec<String>,
+    /// Prepended to the ids of headings (so that the same heading in different tutorials gets a different id)
+    pub anchor_prefix: String,
+    /// Every id given out so far, these must be unique within the page
+    anchors: Vec<String>,
+    /// The `course:` links found so far, they are resolved when the page is built
+    pub links: Vec<CourseLink>,
+}
+
+impl<'a> MarkdownRenderer<'a> {
+    pub fn new(settings: &'a MarkdownSettings) -> MarkdownRenderer<'a> {
+        MarkdownRenderer {
+            settings,
+            warnings: Vec::new(),
+            anchor_prefix: String::new(),
+            anchors: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Reserves an id on this page, returns false if it has already been used
+    pub fn claim_anchor(&mut self, id: &str) -> bool {
+        if self.anchors.iter().any(|anchor| anchor == id) {
+            return false;
+        }
+
+        self.anchors.push(id.to_string());
+
+        true
+    }
+
+    /// Returns an id based on the slug that hasn't been used yet on this page (by appending a number if needed)
+    pub fn unique_anchor(&mut self, slug: &str) -> String {
+        let mut anchor = slug.to_string();
+        let mut n = 1;
+
+        while !self.claim_anchor(&anchor) {
+            n += 1;
+            anchor = format!("{}-{}", slug, n);
+        }
+
+        anchor
+    }
+
+    /// Renders a markdown string into HTML.
+    pub fn render(&mut self, markdown: &str, context: &str) -> Result<String, ParseError> {
+        self.render_with_headings(markdown, context)
+            .map(|(html, _)| html)
+    }
+
+    /// Renders a markdown string into HTML, also returning the headings it contains (for a table of contents).
+    ///
+    /// Block quotes starting with a callout marker (e.g. `> [!TIP]`) are turned into callout boxes
+    /// and every heading is given an id and a permalink.
+    pub fn render_with_headings(
+        &mut self,
+        markdown: &str,
+        context: &str,
+    ) -> Result<(String, Vec<Heading>), ParseError> {
+        self.check_disabled_extensions(markdown, context);
+
+        let events: Vec<Event> =
+            Parser::new_ext(markdown, extension_options(self.settings)).collect();
+        let mut output = Vec::with_capacity(events.len());
+        let mut headings = Vec::new();
+
+        // For each block quote that is currently open, whether it was turned into a callout
+        let mut block_quotes = Vec::new();
+
+        let mut i = 0;
+        while i < events.len() {
+            match &events[i] {
+                Event::Start(Tag::BlockQuote) => match find_callout_marker(&events[i + 1..]) {
+                    Some((marker, text_events)) => {
+                        let kind = marker.to_lowercase();
+
+                        if !CALLOUT_KINDS.contains(&kind.as_str()) {
+                            return Err(ParseError::InvalidValue(
+                                format!(
+                                    "Unknown callout kind `{}`, expected one of {:?}",
+                                    marker, CALLOUT_KINDS
+                                ),
+                                context.to_string(),
+                            ));
+                        }
+
+                        output.push(Event::Html(
+                            format!(
+                                "<div class=\"callout callout-{}\"><p class=\"callout-title\">{}{}</p>\n",
+                                kind,
+                                kind[..1].to_uppercase(),
+                                &kind[1..]
+                            )
+                            .into(),
+                        ));
+                        block_quotes.push(true);
+
+                        // Skip the block quote start, the paragraph start and the marker
+                        i += 2 + text_events;
+
+                        // Unless the marker was the whole paragraph, the rest of the paragraph is kept
+                        if events[i] != Event::End(Tag::Paragraph) {
+                            output.push(Event::Start(Tag::Paragraph));
+                        }
+
+                        // Skip the end of the paragraph or the line break after the marker
+                        i += 1;
+
+                        continue;
+                    }
+                    None => {
+                        block_quotes.push(false);
+                        output.push(events[i].clone());
+                    }
+                },
+                Event::Start(Tag::Heading(level)) => {
+                    let level = *level;
+                    let end = i + events[i..]
+                        .iter()
+                        .position(|event| *event == Event::End(Tag::Heading(level)))
+                        .expect("Heading was never closed");
+
+                    // The plain text of the heading (without any formatting)
+                    let title: String = events[i + 1..end]
+                        .iter()
+                        .filter_map(|event| match event {
+                            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                            _ => None,
+                        })
+                        .collect();
+
+                    let slug = if self.anchor_prefix.is_empty() {
+                        slugify(&title)
+                    } else {
+                        format!("{}-{}", self.anchor_prefix, slugify(&title))
+                    };
+                    let id = self.unique_anchor(&slug);
+
+                    output.push(Event::Html(format!("<h{} id=\"{}\">", level, id).into()));
+                    output.extend(events[i + 1..end].iter().cloned());
+                    output.push(Event::Html(
+                        format!(
+                             " <a class=\"permalink\" href=\"#{}\" title=\"Link to this section\">#</a></h{}>\n",
+                            id, level
+                        )
+                        .into(),
+                    ));
+
+                    // The text has already been unescaped by the parser and the title is put straight into the page
+                    headings.push(Heading {
+                        id,
+                        title: escape_html(&title),
+                        level,
+                    });
+
+                    i = end + 1;
+                    continue;
+                }
+                Event::Start(Tag::Link(_, href, _)) if href.starts_with(COURSE_LINK_SCHEME) => {
+                    self.links.push(CourseLink {
+                        href: href.to_string(),
+                        context: context.to_string(),
+                    });
+                    output.push(events[i].clone());
+                }
+                Event::End(Tag::BlockQuote) => {
+                    if block_quotes.pop() == Some(true) {
+                        output.push(Event::Html("</div>\n".into()));
+                    } else {
+                        output.push(events[i].clone());
+                    }
+                }
+                event => output.push(event.clone()),
+            }
+
+            i += 1;
+        }
+
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(&mut html, output.into_iter());
+
+        Ok((html, headings))
+    }
+
+    /// Adds a warning for each disabled extension whose syntax is used in the markdown,
+    /// since it would otherwise silently render as plain text.
+    fn check_disabled_extensions(&mut self, markdown: &str, context: &str) {
+        let disabled: Vec<_> = EXTENSIONS
+            .iter()
+            .filter(|(name, _)| !self.settings.is_enabled(name))
+            .collect();
+
+        if disabled.is_empty() {
+            return;
+        }
+
+        // Parse again with every extension enabled to find out which ones are used
+        let mut all_options = Options::empty();
+        for (_, option) in EXTENSIONS {
+            all_options.insert(*option);
+        }
+
+        let mut used = Options::empty();
+
+        for event in Parser::new_ext(markdown, all_options) {
+            match event {
+                Event::Start(Tag::Table(_)) => used.insert(Options::ENABLE_TABLES),
+                Event::Start(Tag::FootnoteDefinition(_)) | Event::FootnoteReference(_) => {
+                    used.insert(Options::ENABLE_FOOTNOTES)
+                }
+                Event::Start(Tag::Strikethrough) => used.insert(Options::ENABLE_STRIKETHROUGH),
+                Event::TaskListMarker(_) => used.insert(Options::ENABLE_TASKLISTS),
+                _ => {}
+            }
+        }
+
+        for (name, option) in disabled {
+            if used.contains(*option) {
+                self.warnings.push(format!(
+                    "The content uses syntax from the `{}` markdown extension but it is disabled, in context {}",
+                    name, context
+                ));
+            }
+        }
+    }
+}
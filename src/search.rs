@@ -0,0 +1,162 @@
+use crate::common::{course_href, json_string, CourseIndex};
+use crate::templates::Tutorial;
+
+/// The name of the search index in the output folder (it is also its url relative to the base url).
+/// It is a script rather than JSON so that it can be loaded when the pages are opened straight from disk.
+pub const SEARCH_INDEX_FILE: &str = "search_index.js";
+
+/// The tags which are inside text, the others separate words when they are removed
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "code", "del", "em", "i", "kbd", "mark", "s", "span", "strong", "sub", "sup",
+];
+
+/// Decodes an HTML entity (without the `&` and `;`), None if it isn't one that is decoded
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+            u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(std::char::from_u32)
+        }
+        _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(std::char::from_u32),
+        _ => None,
+    }
+}
+
+/// Converts rendered HTML into plain text for searching,
+/// the tags are removed, entities are decoded and whitespace is collapsed into single spaces
+pub fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => {
+                let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+                let name: String = rest[1..end]
+                    .trim_start_matches('/')
+                    .chars()
+                    .take_while(char::is_ascii_alphanumeric)
+                    .collect();
+
+                // Other tags are replaced by a space so that the words either side of them aren't joined
+                if !INLINE_TAGS.contains(&name.to_ascii_lowercase().as_str()) {
+                    text.push(' ');
+                }
+
+                rest = &rest[end..];
+            }
+            '&' => {
+                let decoded = rest
+                    .find(';')
+                    .and_then(|end| decode_entity(&rest[1..end]).map(|decoded| (decoded, end + 1)));
+
+                match decoded {
+                    Some((decoded, len)) => {
+                        text.push(decoded);
+                        rest = &rest[len..];
+                    }
+                    None => {
+                        text.push('&');
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            c => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Splits the content of a tutorial at its headings, so that search results can link to the section that matched.
+/// Returns the heading (empty for the part before the first heading), the anchor and the HTML of each section.
+fn sections(tutorial: &Tutorial) -> Vec<(&str, &str, &str)> {
+    let content = &tutorial.content;
+
+    // Where each heading starts and where the content after it starts
+    let mut starts = Vec::new();
+
+    for heading in &tutorial.headings {
+        let open = format!("<h{} id=\"{}\">", heading.level, heading.id);
+        let close = format!("</h{}>", heading.level);
+
+        if let Some(start) = content.find(&open) {
+            if let Some(end) = content[start..].find(&close) {
+                starts.push((start, start + end + close.len(), heading));
+            }
+        }
+    }
+
+    let mut sections = vec![(
+        "",
+        tutorial.id.as_str(),
+        &content[..starts.first().map_or(content.len(), |(start, _, _)| *start)],
+    )];
+
+    for (i, (_, body_start, heading)) in starts.iter().enumerate() {
+        let end = starts
+            .get(i + 1)
+            .map_or(content.len(), |(next_start, _, _)| *next_start);
+
+        sections.push((
+            heading.title.as_str(),
+            heading.id.as_str(),
+            &content[*body_start..end.max(*body_start)],
+        ));
+    }
+
+    sections
+}
+
+/// Builds the search index of every listed course (unlisted courses are left out), it is a script which sets
+/// `tutorialWebSearchIndex` to `{"courses": [[title, href]...], "sections": [[course, subtitle, heading, anchor, text]...]}`
+/// where `course` is the position of the course in `courses`
+pub fn search_index(course_index: &CourseIndex, base_url: &str) -> String {
+    let mut courses = Vec::new();
+    let mut sections_json = Vec::new();
+
+    for (group_name, group_courses) in course_index {
+        for (url, course) in group_courses {
+            if course.unlisted {
+                continue;
+            }
+
+            let href = course_href(base_url, &format!("{}/{}", group_name, url));
+
+            for tutorial in &course.tutorials {
+                for (heading, anchor, html) in sections(tutorial) {
+                    sections_json.push(format!(
+                        "[{},{},{},{},{}]",
+                        courses.len(),
+                        json_string(&tutorial.subtitle),
+                        json_string(&plain_text(heading)),
+                        json_string(anchor),
+                        json_string(&plain_text(html))
+                    ));
+                }
+            }
+
+            courses.push(format!(
+                "[{},{}]",
+                json_string(&course.title),
+                json_string(&href)
+            ));
+        }
+    }
+
+    format!(
+        "window.tutorialWebSearchIndex = {{\"courses\":[{}],\"sections\":[{}]}};\n",
+        courses.join(","),
+        sections_json.join(",\n")
+    )
+}
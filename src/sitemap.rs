@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::common::{course_href, CourseFile, CourseGroup};
+use crate::templates::{RobotsTxt, Sitemap, SitemapEntry};
+
+/// The name of the sitemap in the output folder (it is also its url relative to the base url)
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+
+/// The name of the robots.txt file in the output folder
+pub const ROBOTS_FILE: &str = "robots.txt";
+
+/// The date (YYYY-MM-DD, in UTC) of a point in time, times before 1970 are treated as 1970-01-01
+pub fn format_date(time: SystemTime) -> String {
+    let days = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Converts the number of days since 1970-01-01 into a date in the (proleptic) Gregorian calendar,
+    // the calculation starts from 0000-03-01 so that leap days are at the end of the year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The date and time (RFC 3339, in UTC) of a point in time, e.g. `2019-10-05T14:30:00Z`
+pub fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_date(time),
+        seconds % 86_400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// When the course file or any of its assets was last modified
+pub fn last_modified(course_file: &CourseFile) -> io::Result<SystemTime> {
+    use walkdir::WalkDir;
+
+    let mut course_path = course_file.path.clone().into_os_string();
+    course_path.push(".yml");
+
+    let mut modified = fs::metadata(course_path)?.modified()?;
+
+    if course_file.path.is_dir() {
+        for entry in WalkDir::new(&course_file.path) {
+            modified = modified.max(entry?.metadata()?.modified()?);
+        }
+    }
+
+    Ok(modified)
+}
+
+/// Lists the home page and the page of every course that search engines should find,
+/// drafts and unlisted courses are left out.
+/// The date a course was last changed is its `updated` date (it is left out if there isn't one),
+/// file times aren't used so that building the same courses always gives the same sitemap.
+pub fn sitemap(
+    course_groups: &BTreeMap<String, CourseGroup>,
+    site_origin: &str,
+    base_url: &str,
+) -> Sitemap {
+    let mut courses = Vec::new();
+
+    for (group_name, group) in course_groups {
+        for (url, course_file) in &group.courses {
+            let course = &course_file.course;
+
+            if course.draft || course.unlisted {
+                continue;
+            }
+
+            courses.push(SitemapEntry {
+                loc: format!(
+                    "{}{}",
+                    site_origin,
+                    course_href(base_url, &format!("{}/{}", group_name, url))
+                ),
+                lastmod: course.updated.clone(),
+            });
+        }
+    }
+
+    // The home page changes whenever a course on it does (the dates are YYYY-MM-DD so they sort as text)
+    let home = SitemapEntry {
+        loc: format!("{}{}/", site_origin, base_url),
+        lastmod: courses
+            .iter()
+            .filter_map(|entry| entry.lastmod.clone())
+            .max(),
+    };
+
+    Sitemap {
+        entries: std::iter::once(home).chain(courses).collect(),
+    }
+}
+
+/// Allows everything and points to the sitemap
+pub fn robots_txt(site_origin: &str, base_url: &str) -> RobotsTxt {
+    RobotsTxt {
+        sitemap_url: format!("{}{}/{}", site_origin, base_url, SITEMAP_FILE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// The time `days` days (and `seconds` seconds) after 1970-01-01
+    fn day(days: u64, seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(days * 86_400 + seconds)
+    }
+
+    #[test]
+    fn format_date_epoch() {
+        assert_eq!(format_date(UNIX_EPOCH), "1970-01-01");
+        assert_eq!(format_date(day(0, 86_399)), "1970-01-01");
+        assert_eq!(format_date(day(1, 0)), "1970-01-02");
+        assert_eq!(
+            format_date(UNIX_EPOCH - Duration::from_secs(1)),
+            "1970-01-01"
+        );
+    }
+
+    #[test]
+    fn format_date_leap_years() {
+        assert_eq!(format_date(day(18_321, 0)), "2020-02-29");
+        assert_eq!(format_date(day(18_322, 0)), "2020-03-01");
+        assert_eq!(format_date(day(11_016, 0)), "2000-02-29");
+        // 2100 isn't a leap year since it is divisible by 100 but not 400
+        assert_eq!(format_date(day(47_540, 0)), "2100-02-28");
+        assert_eq!(format_date(day(47_541, 0)), "2100-03-01");
+    }
+
+    #[test]
+    fn format_date_year_boundary() {
+        assert_eq!(format_date(day(18_261, 86_399)), "2019-12-31");
+        assert_eq!(format_date(day(18_262, 0)), "2020-01-01");
+    }
+
+    #[test]
+    fn format_timestamp_time_of_day() {
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_timestamp(day(18_321, 14 * 3600 + 30 * 60 + 5)),
+            "2020-02-29T14:30:05Z"
+        );
+    }
+}
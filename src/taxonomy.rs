@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use crate::assets::Assets;
+use crate::common::{home_groups, slugify, CourseGroup, CourseIndex};
+use crate::templates::{Course, Home, Overview, OverviewEntry};
+
+/// The ways courses can be browsed other than by group
+#[derive(Debug, Clone, Copy)]
+pub enum Taxonomy {
+    Tags,
+    Authors,
+}
+
+/// The slug of a tag (or author) used in urls, e.g. `Python basics` becomes `python-basics`.
+/// `+` and `#` are spelled out so that tags like `C`, `C++` and `C#` get their own pages.
+pub fn term_slug(term: &str) -> String {
+    let mut spelled_out = String::with_capacity(term.len());
+
+    for c in term.chars() {
+        match c {
+            '+' => spelled_out.push_str(" plus "),
+            '#' => spelled_out.push_str(" sharp "),
+            c => spelled_out.push(c),
+        }
+    }
+
+    slugify(&spelled_out)
+}
+
+impl Taxonomy {
+    pub const ALL: [Taxonomy; 2] = [Taxonomy::Tags, Taxonomy::Authors];
+
+    /// The folder (in the output and in urls) containing the pages, e.g. `tags/python/index.html`
+    pub fn folder(self) -> &'static str {
+        match self {
+            Taxonomy::Tags => "tags",
+            Taxonomy::Authors => "authors",
+        }
+    }
+
+    /// The title of the overview page
+    fn title(self) -> &'static str {
+        match self {
+            Taxonomy::Tags => "Tags",
+            Taxonomy::Authors => "Authors",
+        }
+    }
+
+    /// The heading of the page listing the courses for one tag or author
+    fn heading(self, name: &str) -> String {
+        match self {
+            Taxonomy::Tags => format!("Courses tagged {}", name),
+            Taxonomy::Authors => format!("Courses by {}", name),
+        }
+    }
+
+    fn terms(self, course: &Course) -> &[String] {
+        match self {
+            Taxonomy::Tags => &course.tags,
+            Taxonomy::Authors => &course.authors,
+        }
+    }
+
+    /// Groups the courses by each of their tags (or authors), the key is the slug used in the url.
+    /// Tags which only differ in case or punctuation (other than `+` and `#`) share a page,
+    /// the name shown is the first one found.
+    /// Unlisted courses are left out.
+    pub fn index(self, course_index: &CourseIndex) -> BTreeMap<String, (String, CourseIndex)> {
+        let mut index: BTreeMap<String, (String, CourseIndex)> = BTreeMap::new();
+
+        for (group_name, courses) in course_index {
+            for (url, course) in courses.iter().filter(|(_, course)| !course.unlisted) {
+                for term in self.terms(course) {
+                    index
+                        .entry(term_slug(term))
+                        .or_insert_with(|| (term.clone(), BTreeMap::new()))
+                        .1
+                        .entry(group_name.clone())
+                        .or_default()
+                        .insert(url.clone(), course.clone());
+                }
+            }
+        }
+
+        index
+    }
+
+    /// The page listing every tag (or author) along with how many courses use it
+    pub fn overview(self, base_url: &str, assets: &Assets, course_index: &CourseIndex) -> Overview {
+        let entries = self
+            .index(course_index)
+            .into_iter()
+            .map(|(slug, (name, courses))| OverviewEntry {
+                name,
+                url: format!("{}/{}/{}/index.html", base_url, self.folder(), slug),
+                count: courses.values().map(BTreeMap::len).sum(),
+            })
+            .collect();
+
+        Overview {
+            base_url: base_url.to_string(),
+            assets: assets.clone(),
+            title: self.title().to_string(),
+            entries,
+        }
+    }
+
+    /// The page listing the courses with a tag (or author), it uses the same layout as the home page
+    pub fn page(
+        self,
+        base_url: &str,
+        assets: &Assets,
+        course_groups: &BTreeMap<String, CourseGroup>,
+        name: &str,
+        courses: CourseIndex,
+    ) -> Home {
+        Home {
+            base_url: base_url.to_string(),
+            assets: assets.clone(),
+            heading: Some(self.heading(name)),
+            course_groups: home_groups(course_groups, courses),
+            feeds: false,
+        }
+    }
+}
@@ -1,12 +1,69 @@
 use askama::Template;
-use std::collections::HashMap;
+
+use crate::assets::Assets;
+use crate::config::MarkdownSettings;
+
+/// Custom filters for the templates
+mod filters {
+    use std::fmt;
+
+    /// Escapes text so that it can be put inside a (double quoted) HTML attribute,
+    /// the templates don't escape anything by default since the content is already HTML
+    pub fn attr<T: fmt::Display>(value: T) -> askama::Result<String> {
+        Ok(crate::common::escape_html(&value.to_string()))
+    }
+
+    /// Converts a tag or author into the form used in urls (see `taxonomy::term_slug`)
+    pub fn term_slug<T: fmt::Display>(value: T) -> askama::Result<String> {
+        Ok(crate::taxonomy::term_slug(&value.to_string()))
+    }
+}
 
 #[derive(Template, Clone)]
 #[template(path = "course.html", escape = "none")]
 /// Struct used for rendering a course
 pub struct Page {
     pub base_url: String,
+    /// The names of the static files the page links to
+    pub assets: Assets,
     pub course: Course,
+    /// The groups containing this course, starting from the top level
+    pub breadcrumbs: Vec<Breadcrumb>,
+    /// The courses that should be completed first
+    pub prerequisites: Vec<NavLink>,
+    /// The learning paths that contain this course
+    pub paths: Vec<PathNav>,
+    /// The courses either side of this one in its group
+    pub previous_course: Option<NavLink>,
+    pub next_course: Option<NavLink>,
+}
+
+#[derive(Debug, Clone)]
+/// A link to another page
+pub struct NavLink {
+    pub title: String,
+    pub href: String,
+}
+
+#[derive(Debug, Clone)]
+/// Where a course is in a learning path, along with the courses either side of it
+pub struct PathNav {
+    /// A link to the page of the path
+    pub path: NavLink,
+    /// The position of the course in the path (starting from 1)
+    pub position: usize,
+    /// The number of courses in the path
+    pub total: usize,
+    pub previous: Option<NavLink>,
+    pub next: Option<NavLink>,
+}
+
+#[derive(Debug, Clone)]
+/// A link to a group on the home page
+pub struct Breadcrumb {
+    pub title: String,
+    /// The id of the group on the home page
+    pub anchor: String,
 }
 
 #[derive(Debug, Clone)]
@@ -16,8 +73,59 @@ pub struct Course {
     pub url: String,
     pub tutorials: Vec<Tutorial>,
     pub lang: String,
+    /// Courses with a lower weight are listed first within their group, this defaults to 0
+    pub weight: i64,
+    /// A short plain text summary, shown in listings and link previews
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub tags: Vec<String>,
+    /// One of `parse::DIFFICULTIES`
+    pub difficulty: Option<String>,
+    /// Roughly how long the course takes to work through
+    pub estimated_minutes: Option<u32>,
+    /// When the course was last changed, as a `YYYY-MM-DD` date
+    pub updated: Option<String>,
+    /// Drafts are shown by the test server but left out of the build (unless drafts are included)
+    pub draft: bool,
+    /// Unlisted courses are built but left out of the listings, so they can only be reached by their url
+    pub unlisted: bool,
+    /// The courses (as `group/url`) that should be completed before this one
+    pub prerequisites: Vec<String>,
+    /// Old urls of the course (as `url`, or `group/url` if it was in another group), they redirect to the course
+    pub aliases: Vec<String>,
     /// Sets the course wide tutorial settings (not required)
     pub tutorial_settings: CourseTutorialSettings,
+    /// Non fatal problems found while parsing the course
+    pub warnings: Vec<String>,
+    /// The `course:` links in the content (these are checked once every course has been parsed)
+    pub links: Vec<CourseLink>,
+}
+
+impl Course {
+    /// The estimated time formatted for people, e.g. `1 h 30 min`
+    pub fn estimated_duration(&self) -> Option<String> {
+        self.estimated_minutes
+            .map(|minutes| match (minutes / 60, minutes % 60) {
+                (0, minutes) => format!("{} min", minutes),
+                (hours, 0) => format!("{} h", hours),
+                (hours, minutes) => format!("{} h {} min", hours, minutes),
+            })
+    }
+
+    /// Whether there is a tutorial or a heading in the content with this id
+    pub fn has_anchor(&self, id: &str) -> bool {
+        self.tutorials.iter().any(|tutorial| {
+            tutorial.id == id || tutorial.headings.iter().any(|heading| heading.id == id)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A link to another course (or another part of the same course), e.g. `course:python/introduction#variables`
+pub struct CourseLink {
+    pub href: String,
+    /// Where the link was found (for error messages)
+    pub context: String,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +135,10 @@ pub struct CourseTutorialSettings {
     pub start_closed: bool,
     /// This defaults to true
     pub show_number: bool,
+    /// When false the solutions are left out of the output entirely, this defaults to true
+    pub include_solutions: bool,
+    /// Defaults to the site wide markdown settings
+    pub markdown: MarkdownSettings,
 }
 
 impl std::default::Default for CourseTutorialSettings {
@@ -34,6 +146,8 @@ impl std::default::Default for CourseTutorialSettings {
         CourseTutorialSettings {
             start_closed: false,
             show_number: true,
+            include_solutions: true,
+            markdown: MarkdownSettings::default(),
         }
     }
 }
@@ -42,15 +156,209 @@ impl std::default::Default for CourseTutorialSettings {
 #[template(path = "home.html", escape = "none")]
 pub struct Home {
     pub base_url: String,
-    pub course_groups: HashMap<String, HashMap<String, Course>>,
+    /// The names of the static files the page links to
+    pub assets: Assets,
+    /// Shown above the listing when it isn't the home page (e.g. the courses with a tag)
+    pub heading: Option<String>,
+    /// The groups in the order they are shown
+    pub course_groups: Vec<HomeGroup>,
+    /// Whether to link to the Atom feeds (they are only built when the site origin is known)
+    pub feeds: bool,
+}
+
+#[derive(Debug, Clone)]
+/// An ordered list of courses to work through, from a file in the `paths` folder
+pub struct LearningPath {
+    /// The name of the file (excluding the .yml), this is used in urls
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// The courses (as `group/url`) in the order they should be completed
+    pub courses: Vec<String>,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "path.html", escape = "none")]
+/// Struct used for rendering the page of a learning path
+pub struct PathPage {
+    pub base_url: String,
+    /// The names of the static files the page links to
+    pub assets: Assets,
+    pub path: LearningPath,
+    /// The link to each course in the path along with the course (courses that don't exist are left out)
+    pub courses: Vec<(String, Course)>,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "overview.html", escape = "none")]
+/// Struct used for rendering the list of every tag (or author)
+pub struct Overview {
+    pub base_url: String,
+    /// The names of the static files the page links to
+    pub assets: Assets,
+    pub title: String,
+    pub entries: Vec<OverviewEntry>,
+}
+
+#[derive(Debug, Clone)]
+/// A link to the page listing the courses for one tag (or author)
+pub struct OverviewEntry {
+    pub name: String,
+    pub url: String,
+    /// The number of courses on the page
+    pub count: usize,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "redirect.html", escape = "none")]
+/// Struct used for rendering the page left at an old url of a course
+pub struct Redirect {
+    /// The title of the course
+    pub title: String,
+    /// Where the course is now
+    pub href: String,
+    /// The full url of the course if the site origin is known (otherwise the same as `href`)
+    pub canonical: String,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "sitemap.xml", escape = "none")]
+/// Struct used for rendering the sitemap (the list of public pages for search engines)
+pub struct Sitemap {
+    pub entries: Vec<SitemapEntry>,
+}
+
+#[derive(Debug, Clone)]
+/// A page in the sitemap
+pub struct SitemapEntry {
+    /// The full url of the page
+    pub loc: String,
+    /// When the page was last changed (YYYY-MM-DD)
+    pub lastmod: Option<String>,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "feed.xml", escape = "none")]
+/// Struct used for rendering an Atom feed of the courses (of the whole site or one group)
+pub struct Feed {
+    pub title: String,
+    /// The full url of the feed, it is also its id
+    pub self_url: String,
+    /// The full url of the page listing the same courses
+    pub page_url: String,
+    /// Atom needs an author for the feed since not every course has one
+    pub author: String,
+    /// When the most recently updated course was updated (RFC 3339)
+    pub updated: String,
+    /// The most recently updated courses first
+    pub entries: Vec<FeedEntry>,
+}
+
+#[derive(Debug, Clone)]
+/// A course in a feed
+pub struct FeedEntry {
+    pub title: String,
+    /// The full url of the course, it is also its id
+    pub url: String,
+    /// When the course was last updated (RFC 3339)
+    pub updated: String,
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Template, Clone)]
+#[template(path = "robots.txt", escape = "none")]
+/// Struct used for rendering the robots.txt file
+pub struct RobotsTxt {
+    /// The full url of the sitemap
+    pub sitemap_url: String,
+}
+
+#[derive(Debug, Clone)]
+/// A course group as listed on the home page
+pub struct HomeGroup {
+    /// The path of the group folder from the course folder (used in urls)
+    pub name: String,
+    /// The id of the group on the home page
+    pub anchor: String,
+    /// How deeply nested the group is (top level groups are 0)
+    pub depth: usize,
+    pub info: GroupInfo,
+    /// The url and course of each course in the group (in the order they are shown)
+    pub courses: Vec<(String, Course)>,
+}
+
+#[derive(Debug, Clone)]
+/// Information about a course group, read from the `_group.yml` file in the group folder (if there is one)
+pub struct GroupInfo {
+    /// Defaults to the name of the group folder
+    pub title: String,
+    pub description: Option<String>,
+    /// Path to an image relative to the group folder
+    pub image: Option<String>,
+    /// Groups with a lower weight are shown first, this defaults to 0
+    pub weight: i64,
+    /// The names of course files (excluding the .yml), these courses are shown first in this order
+    pub course_order: Vec<String>,
+}
+
+impl GroupInfo {
+    /// The default information for the group folder with this name (the title is the last folder in the path)
+    pub fn new(name: &str) -> GroupInfo {
+        GroupInfo {
+            title: name.rsplit('/').next().unwrap_or(name).to_string(),
+            description: None,
+            image: None,
+            weight: 0,
+            course_order: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Tutorial {
+    /// Unique within the course, used for links and saved progress (defaults to a slug of the subtitle)
+    pub id: String,
     pub subtitle: String,
     pub content: String,
+    /// The headings within the content, used for the table of contents
+    pub headings: Vec<Heading>,
     /// Defaults to the course wide settings
     pub start_closed: Option<bool>,
     /// Defaults to the course wide settings
     pub show_number: Option<bool>,
+    /// The questions to check understanding of this tutorial (may be empty)
+    pub quiz: Vec<QuizQuestion>,
+    /// HTML renders of the hints, each one is revealed separately
+    pub hints: Vec<String>,
+    /// HTML render of the model solution
+    pub solution: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+/// A heading within the content of a tutorial
+pub struct Heading {
+    pub id: String,
+    /// The plain text of the heading (HTML escaped)
+    pub title: String,
+    pub level: u32,
+}
+
+#[derive(Debug, Clone)]
+/// A single multiple choice question, graded in the browser
+pub struct QuizQuestion {
+    /// HTML render of the question
+    pub question: String,
+    pub options: Vec<QuizOption>,
+    /// HTML render of the explanation shown once the question has been answered
+    pub explanation: Option<String>,
+    /// When true more than one option is correct (so the options are checkboxes rather than radio buttons)
+    pub multiple_answers: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuizOption {
+    /// HTML render of the option
+    pub content: String,
+    pub correct: bool,
 }
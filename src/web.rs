@@ -3,41 +3,67 @@ use askama::Template;
 use actix_files as fs;
 use actix_web::{web, App, Either, HttpRequest, HttpResponse, HttpServer, Responder};
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
 
-use crate::templates::{Course, Home, Page};
+use crate::assets::Assets;
+use crate::common::{
+    breadcrumbs, course_aliases, home_groups, prerequisite_links, resolve_course_links,
+    sibling_links, CourseGroup, CourseIndex,
+};
+use crate::config::{load_config, SiteConfig};
+use crate::learning_paths::{path_navs, path_page, paths_overview};
+use crate::search::{search_index, SEARCH_INDEX_FILE};
+use crate::taxonomy::Taxonomy;
+use crate::templates::{Home, LearningPath, Page};
 
-fn render_home(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder {
-    let mut course_groups: HashMap<String, HashMap<String, Course>> = HashMap::new();
-
-    for (course_group_name, course_group_map) in &state.course_urls {
-        for (course_name, course_path) in course_group_map {
-            let course_str = match std::fs::read_to_string(course_path.with_extension("yml")) {
-                Ok(str) => str,
-                Err(_) => {
-                    return Either::B(format!(
-                        "Couldn't open and read course file: {:?}",
-                        course_path
-                    ))
-                }
-            };
+/// Parses every course (from disk so that edits are picked up on reload).
+/// If `strict_mode` isn't set the courses that can't be read or parsed are printed and left out.
+fn load_course_index(state: &AppState, strict_mode: bool) -> Result<CourseIndex, String> {
+    let mut course_groups: CourseIndex = BTreeMap::new();
 
-            let course = match crate::parse::parse_course(&course_str) {
+    for (course_group_name, course_group) in &state.course_urls {
+        for (course_name, course_file) in &course_group.courses {
+            let course_path = &course_file.path;
+            let course = std::fs::read_to_string(course_path.with_extension("yml"))
+                .map_err(|_| format!("Couldn't open and read course file: {:?}", course_path))
+                .and_then(|course_str| {
+                    crate::parse::parse_course(&course_str, &state.site_config)
+                        .map_err(|_| format!("Couldn't parse yaml file: {:?}", course_path))
+                });
+
+            let course = match course {
                 Ok(course) => course,
-                Err(_) => return Either::B(format!("Couldn't parse yaml file: {:?}", course_path)),
+                Err(msg) if strict_mode => return Err(msg),
+                Err(msg) => {
+                    println!("WARNING ========= {}\n", msg);
+                    continue;
+                }
             };
 
             course_groups
                 .entry(course_group_name.clone())
-                .or_insert_with(HashMap::new)
+                .or_default()
                 .insert(course_name.to_string(), course);
         }
     }
 
+    Ok(course_groups)
+}
+
+fn render_home(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder {
+    let course_groups = match load_course_index(&state, true) {
+        Ok(course_groups) => course_groups,
+        Err(msg) => return Either::B(msg),
+    };
+
     let home = Home {
         base_url: "".to_string(),
-        course_groups,
+        // The test server doesn't fingerprint the static files
+        assets: Assets::default(),
+        heading: None,
+        course_groups: home_groups(&state.course_urls, course_groups),
+        // The test server doesn't serve the feeds
+        feeds: false,
     };
 
     match home.render() {
@@ -46,15 +72,136 @@ fn render_home(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder
     }
 }
 
-fn redirect_course(_state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+/// Renders the list of every tag (or author)
+fn render_overview(state: web::Data<AppState>, taxonomy: Taxonomy) -> Either<HttpResponse, String> {
+    let course_index = match load_course_index(&state, true) {
+        Ok(course_index) => course_index,
+        Err(msg) => return Either::B(msg),
+    };
+
+    match taxonomy
+        .overview("", &Assets::default(), &course_index)
+        .render()
+    {
+        Ok(res) => Either::A(HttpResponse::Ok().body(res)),
+        Err(_) => Either::B("Couldn't render overview into html".to_string()),
+    }
+}
+
+/// Renders the list of courses with a tag (or author), the slug is taken from the url
+fn render_listing(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    taxonomy: Taxonomy,
+) -> Either<HttpResponse, String> {
+    let slug = req
+        .match_info()
+        .get("slug")
+        .expect("Missing parameters from routes");
+
+    let course_index = match load_course_index(&state, true) {
+        Ok(course_index) => course_index,
+        Err(msg) => return Either::B(msg),
+    };
+
+    match taxonomy.index(&course_index).remove(slug) {
+        Some((name, courses)) => {
+            match taxonomy
+                .page("", &Assets::default(), &state.course_urls, &name, courses)
+                .render()
+            {
+                Ok(res) => Either::A(HttpResponse::Ok().body(res)),
+                Err(_) => Either::B("Couldn't render listing into html".to_string()),
+            }
+        }
+        None => Either::A(
+            HttpResponse::NotFound().body(format!("No courses were found for `{}`", slug)),
+        ),
+    }
+}
+
+fn render_paths_overview(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder {
+    match paths_overview(&state.paths, "", &Assets::default()).render() {
+        Ok(res) => Either::A(HttpResponse::Ok().body(res)),
+        Err(_) => Either::B("Couldn't render overview into html".to_string()),
+    }
+}
+
+fn render_path(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let name = req
+        .match_info()
+        .get("name")
+        .expect("Missing parameters from routes");
+
+    let path = match state.paths.get(name) {
+        Some(path) => path,
+        None => {
+            return Either::A(HttpResponse::NotFound().body(
+                "The learning path wasn't found, if you have recently created the file try restarting the server",
+            ))
+        }
+    };
+
+    let course_index = match load_course_index(&state, true) {
+        Ok(course_index) => course_index,
+        Err(msg) => return Either::B(msg),
+    };
+
+    match path_page(path, &course_index, "", &Assets::default()).render() {
+        Ok(res) => Either::A(HttpResponse::Ok().body(res)),
+        Err(_) => Either::B("Couldn't render learning path into html".to_string()),
+    }
+}
+
+/// Builds the search index from the current version of every course
+fn serve_search_index(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder {
+    match load_course_index(&state, true) {
+        Ok(course_index) => Either::A(
+            HttpResponse::Ok()
+                .content_type("application/javascript")
+                .body(search_index(&course_index, "")),
+        ),
+        Err(msg) => Either::B(msg),
+    }
+}
+
+/// Redirects to the page of a course, `reference` is the `group/url` of the course
+fn course_redirect(reference: &str) -> HttpResponse {
     use actix_web::http::header::LOCATION;
 
+    HttpResponse::PermanentRedirect()
+        .header(LOCATION, format!("/course/{}/index.html", reference))
+        .finish()
+}
+
+fn redirect_course(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
     let topic = req.match_info().get("topic").unwrap();
     let name = req.match_info().get("name").unwrap();
+    let reference = format!("{}/{}", topic, name);
 
-    HttpResponse::PermanentRedirect()
-        .header(LOCATION, format!("/course/{}/{}/index.html", topic, name))
-        .finish()
+    // Old urls of a course go straight to its current url
+    course_redirect(state.aliases.get(&reference).unwrap_or(&reference))
+}
+
+/// The page shown for any url that doesn't match a route, it lists every course like the home page
+fn render_not_found(state: web::Data<AppState>, _req: HttpRequest) -> impl Responder {
+    let course_groups = match load_course_index(&state, true) {
+        Ok(course_groups) => course_groups,
+        Err(msg) => return Either::B(msg),
+    };
+
+    let not_found = Home {
+        base_url: "".to_string(),
+        assets: Assets::default(),
+        heading: Some("Page not found".to_string()),
+        course_groups: home_groups(&state.course_urls, course_groups),
+        feeds: false,
+    };
+
+    match not_found.render() {
+        Ok(res) => Either::A(HttpResponse::NotFound().body(res)),
+        Err(_) => Either::B("Couldn't render the 404 page into html".to_string()),
+    }
 }
 
 fn render_course(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
@@ -63,26 +210,49 @@ fn render_course(state: web::Data<AppState>, req: HttpRequest) -> impl Responder
         if let Some(path) = state
             .course_urls
             .get(topic)
-            .and_then(|course_groups| course_groups.get(name))
+            .and_then(|course_group| course_group.courses.get(name))
+            .map(|course_file| &course_file.path)
         {
             match std::fs::read_to_string(path.with_extension("yml"))
                 .map_err(|_| "Couldn't open and read file".to_string())
                 .and_then(|course_str| {
-                    crate::parse::parse_course(&course_str)
+                    crate::parse::parse_course(&course_str, &state.site_config)
                         .map_err(|err| format!("Couldn't parse yaml file: {}", err))
                 })
+                .inspect(|course| {
+                    // Edits are picked up on reload so the warnings are shown here as well as at startup
+                    for warning in &course.warnings {
+                        println!("WARNING ========= {:?}\n{}\n", path, warning);
+                    }
+                })
                 .and_then(|course| {
+                    // The other courses are needed for the titles of the prerequisites and the navigation,
+                    // any that can't be parsed are left out so that a mistake in one doesn't break every page
+                    let course_index = load_course_index(&state, false)?;
+                    let reference = format!("{}/{}", topic, name);
+                    let (previous_course, next_course) =
+                        sibling_links(&state.course_urls, &course_index, topic, name, "");
+
                     let page = Page {
                         base_url: "".to_string(),
+                        assets: Assets::default(),
+                        breadcrumbs: breadcrumbs(&state.course_urls, topic),
+                        prerequisites: prerequisite_links(&course, &course_index, ""),
+                        paths: path_navs(&reference, &state.paths, &course_index, ""),
+                        previous_course,
+                        next_course,
                         course,
                     };
 
                     page.render()
+                        .map(|html| resolve_course_links(&html, &state.course_urls, ""))
                         .map_err(|_| "Couldn't render course into html".to_string())
                 }) {
                 Ok(result) => Either::A(HttpResponse::Ok().body(result)),
                 Err(msg) => Either::B(msg),
             }
+        } else if let Some(target) = state.aliases.get(&format!("{}/{}", topic, name)) {
+            Either::A(course_redirect(target))
         } else {
             Either::B("The url course wasn't found, if you have recently created the file try restarting the server".to_string())
         }
@@ -109,8 +279,8 @@ fn serve_assets(state: web::Data<AppState>, req: HttpRequest) -> impl Responder
     if let Some(mut path) = state
         .course_urls
         .get(topic)
-        .and_then(|course_group| course_group.get(name))
-        .cloned()
+        .and_then(|course_group| course_group.courses.get(name))
+        .map(|course_file| course_file.path.clone())
     {
         // It is likely possible for an attacker to use this to preform a reverse traversal attack
         // another reason why this code should only be used for local testing
@@ -125,25 +295,67 @@ fn serve_assets(state: web::Data<AppState>, req: HttpRequest) -> impl Responder
     }
 }
 
+fn serve_group_image(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    // If our routes are setup correctly it should be impossible for this to fail
+    let image_path = req
+        .match_info()
+        .get("image_path")
+        .expect("Missing parameters from routes");
+
+    // Groups can be nested so the path is `{group}/{image}` where both may contain slashes
+    let group_image = state
+        .course_urls
+        .iter()
+        .find_map(|(group_name, course_group)| {
+            let image = course_group.info.image.as_ref()?;
+
+            if format!("{}/{}", group_name, image) == image_path {
+                Some(course_group.path.join(image))
+            } else {
+                None
+            }
+        });
+
+    match group_image {
+        // Only the images named in group files are served
+        Some(path) => match fs::NamedFile::open(path) {
+            Ok(file) => Either::A(file),
+            Err(_) => Either::B(HttpResponse::NotFound().body("Couldn't find/open the file")),
+        },
+        None => Either::B(HttpResponse::NotFound().body("The group image wasn't found, if you have recently changed the group file try restarting the server")),
+    }
+}
+
 #[derive(Clone)]
 struct AppState {
-    pub course_urls: HashMap<String, HashMap<String, PathBuf>>,
+    pub course_urls: BTreeMap<String, CourseGroup>,
+    pub site_config: SiteConfig,
+    pub paths: BTreeMap<String, LearningPath>,
+    /// The course (as `group/url`) each alias redirects to
+    pub aliases: BTreeMap<String, String>,
 }
 
 pub fn start_server(port: u16, static_folder: String, course_folder: &str) -> std::io::Result<()> {
     use crate::common::{get_courses, CourseError};
+    use crate::learning_paths::get_paths;
     // Get courses in a non-strict way (if there is an error just skip)
-    let course_urls = match get_courses(course_folder, false) {
-        Ok(val) => val,
-        Err(CourseError::Io(err)) => return Err(err),
-        Err(CourseError::Parse(err)) => {
-            println!(
-                "Server intialisation failed because it couldn't load course files:\n{}",
-                err
-            );
-            std::process::exit(1);
-        }
-    };
+    let (site_config, course_urls, paths) =
+        match load_config(course_folder).and_then(|site_config| {
+            let courses = get_courses(course_folder, &site_config, false, true)?;
+            let paths = get_paths(course_folder, &courses, false)?;
+
+            Ok((site_config, courses, paths))
+        }) {
+            Ok(val) => val,
+            Err(CourseError::Io(err)) => return Err(err),
+            Err(CourseError::Parse(err)) => {
+                println!(
+                    "Server intialisation failed because it couldn't load course files:\n{}",
+                    err
+                );
+                std::process::exit(1);
+            }
+        };
 
     if course_urls.is_empty() {
         println!("Could find any files");
@@ -152,9 +364,9 @@ pub fn start_server(port: u16, static_folder: String, course_folder: &str) -> st
 
     println!("Loaded the following files:");
 
-    for (group_name, courses) in &course_urls {
+    for (group_name, course_group) in &course_urls {
         println!("==={}===", group_name);
-        for (i, course_name) in courses.keys().enumerate() {
+        for (i, course_name) in course_group.courses.keys().enumerate() {
             println!(
                 "{}. http://127.0.0.1:8000/course/{}/{}/index.html",
                 i + 1,
@@ -175,18 +387,48 @@ If you edit a course which is listed here you must simply reload the webpage to
     println!("Use the build command to generate the production files and then serve them.");
     println!("=========");
 
-    let app_state = AppState { course_urls };
+    let app_state = AppState {
+        aliases: course_aliases(&course_urls),
+        course_urls,
+        site_config,
+        paths,
+    };
 
     HttpServer::new(move || {
         App::new()
             .register_data(web::Data::new(app_state.clone()))
             .service(web::resource("/").to(render_home))
-            .service(web::resource("/course/{topic}/{name}").to(redirect_course))
-            .service(web::resource("/course/{topic}/{name}/index.html").to(render_course))
+            // Groups can be nested so the topic can contain slashes, the redirect must come last
+            // since it would also match the other routes
+            .service(web::resource("/course/{topic:.+}/{name}/index.html").to(render_course))
+            .service(
+                web::resource("/course/{topic:.+}/{name}/assets/{asset_path:.*}").to(serve_assets),
+            )
+            .service(web::resource("/course/{topic:.+}/{name}").to(redirect_course))
+            .service(web::resource("/group/{image_path:.*}").to(serve_group_image))
+            .service(
+                web::resource("/tags/index.html")
+                    .to(|state: web::Data<AppState>| render_overview(state, Taxonomy::Tags)),
+            )
+            .service(web::resource("/tags/{slug}/index.html").to(
+                |state: web::Data<AppState>, req: HttpRequest| {
+                    render_listing(state, req, Taxonomy::Tags)
+                },
+            ))
             .service(
-                web::resource("/course/{topic}/{name}/assets/{asset_path:.*}").to(serve_assets),
+                web::resource("/authors/index.html")
+                    .to(|state: web::Data<AppState>| render_overview(state, Taxonomy::Authors)),
             )
+            .service(web::resource("/authors/{slug}/index.html").to(
+                |state: web::Data<AppState>, req: HttpRequest| {
+                    render_listing(state, req, Taxonomy::Authors)
+                },
+            ))
+            .service(web::resource("/paths/index.html").to(render_paths_overview))
+            .service(web::resource("/paths/{name}/index.html").to(render_path))
+            .service(web::resource(&format!("/{}", SEARCH_INDEX_FILE)).to(serve_search_index))
             .service(fs::Files::new("/static", static_folder.clone()).show_files_listing())
+            .default_service(web::route().to(render_not_found))
     })
     .bind(("127.0.0.1", port))
     .expect("Unable to bind address to start web server")
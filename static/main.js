@@ -1,7 +1,110 @@
 // This file is appended at the end so that all DOM elements should have loaded
 
 document.querySelectorAll('h2.subtitle').forEach(function(subtitle) {
-  subtitle.addEventListener('click', function() {
+  subtitle.addEventListener('click', function(event) {
+    // Following a permalink shouldn't close the tutorial
+    if (event.target.classList.contains('permalink')) {
+      return;
+    }
+
     subtitle.parentElement.classList.toggle('hide');
   });
 });
+
+// Opens the tutorial containing the element the url fragment points to (it may start closed)
+function openFragmentTarget() {
+  if (!window.location.hash) {
+    return;
+  }
+
+  var target = document.getElementById(decodeURIComponent(window.location.hash.slice(1)));
+
+  if (!target) {
+    return;
+  }
+
+  var tutorial = target.closest('.tutorial');
+
+  if (tutorial && tutorial.classList.contains('hide')) {
+    tutorial.classList.remove('hide');
+    target.scrollIntoView();
+  }
+}
+
+window.addEventListener('hashchange', openFragmentTarget);
+openFragmentTarget();
+
+// Clicking a link to the current fragment doesn't fire hashchange, so the links are handled directly too
+document.querySelectorAll('a[href^="#"]').forEach(function(link) {
+  link.addEventListener('click', function() {
+    if (link.getAttribute('href') === window.location.hash) {
+      openFragmentTarget();
+    }
+  });
+});
+
+// Progress is saved per page and keyed by the tutorial ids, so it survives tutorials being reordered
+var progressKey = 'tutorial_web:' + window.location.pathname;
+
+function loadProgress() {
+  try {
+    return JSON.parse(window.localStorage.getItem(progressKey)) || {};
+  } catch (e) {
+    return {};
+  }
+}
+
+function saveProgress(progress) {
+  try {
+    window.localStorage.setItem(progressKey, JSON.stringify(progress));
+  } catch (e) {
+    // Storage may be unavailable (e.g. private browsing), progress just isn't saved
+  }
+}
+
+// Quizzes are graded in the browser, each option knows whether it is correct
+document.querySelectorAll('form.quiz-question').forEach(function(question) {
+  var tutorialId = question.closest('.tutorial').dataset.id;
+  var questionNumber = question.dataset.question;
+
+  var result = question.querySelector('.quiz-result');
+
+  var answeredQuestions = loadProgress()[tutorialId] || [];
+  if (answeredQuestions.indexOf(questionNumber) !== -1) {
+    question.classList.add('answered', 'correct');
+    result.textContent = 'You have answered this correctly before.';
+  }
+
+  question.addEventListener('submit', function(event) {
+    event.preventDefault();
+
+    var options = question.querySelectorAll('input[name="answer"]');
+    var answered = false;
+    var correct = true;
+
+    options.forEach(function(option) {
+      answered = answered || option.checked;
+      correct = correct && option.checked === (option.dataset.correct === 'true');
+    });
+
+    if (!answered) {
+      result.textContent = 'Pick an answer first.';
+      return;
+    }
+
+    question.classList.add('answered');
+    question.classList.toggle('correct', correct);
+    question.classList.toggle('incorrect', !correct);
+    result.textContent = correct ? 'Correct!' : 'Not quite, try again.';
+
+    if (correct) {
+      var progress = loadProgress();
+      progress[tutorialId] = progress[tutorialId] || [];
+
+      if (progress[tutorialId].indexOf(questionNumber) === -1) {
+        progress[tutorialId].push(questionNumber);
+        saveProgress(progress);
+      }
+    }
+  });
+});
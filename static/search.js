@@ -0,0 +1,193 @@
+// Searches every course in the browser, the index is only loaded once the search box is used.
+// The index is a script (rather than JSON) so that it also loads when the pages are opened straight from disk.
+
+(function() {
+  var form = document.querySelector('form.search');
+
+  if (!form) {
+    return;
+  }
+
+  var input = form.querySelector('.search-input');
+  var message = form.querySelector('.search-message');
+  var results = form.querySelector('.search-results');
+
+  var maxResults = 20;
+  var sections = null;
+  var loading = false;
+
+  function loadIndex() {
+    if (sections || loading) {
+      return;
+    }
+
+    loading = true;
+
+    var script = document.createElement('script');
+    script.src = form.dataset.index;
+
+    script.onload = function() {
+      var index = window.tutorialWebSearchIndex;
+
+      sections = index.sections.map(function(section) {
+        var course = index.courses[section[0]];
+
+        return {
+          course: course[0],
+          href: course[1] + '#' + section[3],
+          subtitle: section[1],
+          heading: section[2],
+          text: section[4],
+          lowerTitle: (course[0] + ' ' + section[1] + ' ' + section[2]).toLowerCase(),
+          lowerText: section[4].toLowerCase()
+        };
+      });
+
+      search();
+    };
+
+    script.onerror = function() {
+      loading = false;
+      message.textContent = 'The search index couldn\'t be loaded.';
+    };
+
+    document.head.appendChild(script);
+  }
+
+  function escapeRegExp(text) {
+    return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+  }
+
+  // Adds the text to the element with every occurrence of the terms wrapped in <mark>
+  function appendHighlighted(element, text, terms) {
+    var pattern = new RegExp('(' + terms.map(escapeRegExp).join('|') + ')', 'gi');
+
+    // The captured matches are at the odd positions
+    text.split(pattern).forEach(function(part, i) {
+      if (i % 2 === 1) {
+        var mark = document.createElement('mark');
+        mark.textContent = part;
+        element.appendChild(mark);
+      } else {
+        element.appendChild(document.createTextNode(part));
+      }
+    });
+  }
+
+  // The part of the text around the first match
+  function snippet(section, query, terms) {
+    var position = section.lowerText.indexOf(query);
+
+    if (position === -1) {
+      position = section.lowerText.indexOf(terms[0]);
+    }
+
+    var start = Math.max(0, position - 60);
+    var end = Math.min(section.text.length, Math.max(position, 0) + 120);
+
+    return (start > 0 ? '…' : '') + section.text.slice(start, end) + (end < section.text.length ? '…' : '');
+  }
+
+  // Every term must appear somewhere, matches in the titles count for more and so does the whole query appearing
+  function score(section, query, terms) {
+    var total = 0;
+
+    for (var i = 0; i < terms.length; i++) {
+      var inTitle = section.lowerTitle.indexOf(terms[i]) !== -1;
+      var inText = section.lowerText.indexOf(terms[i]) !== -1;
+
+      if (!inTitle && !inText) {
+        return 0;
+      }
+
+      total += (inTitle ? 10 : 0) + (inText ? 1 : 0);
+    }
+
+    if (terms.length > 1 && (section.lowerTitle + ' ' + section.lowerText).indexOf(query) !== -1) {
+      total += 20;
+    }
+
+    return total;
+  }
+
+  function search() {
+    var query = input.value.trim().toLowerCase().replace(/\s+/g, ' ');
+
+    results.textContent = '';
+    message.textContent = '';
+
+    if (!query) {
+      return;
+    }
+
+    if (!sections) {
+      message.textContent = 'Loading…';
+      loadIndex();
+      return;
+    }
+
+    var terms = query.split(' ');
+
+    var matches = sections
+      .map(function(section) {
+        return { section: section, score: score(section, query, terms) };
+      })
+      .filter(function(match) {
+        return match.score > 0;
+      })
+      // The sort isn't always stable so the position in the index breaks ties
+      .map(function(match, i) {
+        match.position = i;
+        return match;
+      })
+      .sort(function(a, b) {
+        return b.score - a.score || a.position - b.position;
+      })
+      .slice(0, maxResults);
+
+    if (matches.length === 0) {
+      message.textContent = 'No results for “' + input.value.trim() + '”';
+      return;
+    }
+
+    matches.forEach(function(match) {
+      var section = match.section;
+      var item = document.createElement('li');
+
+      var link = document.createElement('a');
+      link.href = section.href;
+      appendHighlighted(link, [section.course, section.subtitle, section.heading].filter(Boolean).join(' › '), terms);
+      item.appendChild(link);
+
+      if (section.text) {
+        var text = document.createElement('p');
+        text.className = 'search-result-snippet';
+        appendHighlighted(text, snippet(section, query, terms), terms);
+        item.appendChild(text);
+      }
+
+      results.appendChild(item);
+    });
+  }
+
+  input.addEventListener('focus', loadIndex);
+  input.addEventListener('input', search);
+
+  input.addEventListener('keydown', function(event) {
+    if (event.key === 'Escape') {
+      input.value = '';
+      search();
+    }
+  });
+
+  // Enter goes to the best result
+  form.addEventListener('submit', function(event) {
+    event.preventDefault();
+
+    var first = results.querySelector('a');
+
+    if (first) {
+      window.location.href = first.href;
+    }
+  });
+})();